@@ -102,12 +102,14 @@ impl Apps {
             .await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(&self, app_name: &str) -> Result<AppPermissions, Error> {
         self.client
             .get(&format!("{}/{}", PERMISSIONS_PATH, app_name))
             .await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         app_name: &str,
@@ -120,6 +122,7 @@ impl Apps {
             .await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         app_name: &str,
@@ -130,6 +133,7 @@ impl Apps {
             .await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         app_name: &str,
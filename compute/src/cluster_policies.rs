@@ -51,6 +51,7 @@ impl ClusterPolicies {
 
     // Permissions
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(
         &self,
         cluster_policy_id: &str,
@@ -62,6 +63,7 @@ impl ClusterPolicies {
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         cluster_policy_id: &str,
@@ -73,6 +75,7 @@ impl ClusterPolicies {
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         cluster_policy_id: &str,
@@ -85,6 +88,7 @@ impl ClusterPolicies {
         self.client.put(&path, request).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         cluster_policy_id: &str,
@@ -5,9 +5,9 @@ use crate::types::{
     ListAvailableZonesResponse, ListClustersResponse, ListNodeTypesResponse, ResizeCluster,
     UpdateCluster,
 };
+use databricks_core::runtime::Instant;
 use databricks_core::{Client, Error};
 use std::time::Duration;
-use tokio::time::sleep;
 
 const PATH: &str = "/api/2.0/clusters";
 
@@ -173,11 +173,13 @@ impl Clusters {
 
     // Permissions
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(&self, cluster_id: &str) -> Result<ClusterPermissions, Error> {
         let path = format!("/api/2.0/permissions/clusters/{}", cluster_id);
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         cluster_id: &str,
@@ -189,6 +191,7 @@ impl Clusters {
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         cluster_id: &str,
@@ -198,6 +201,7 @@ impl Clusters {
         self.client.put(&path, request).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         cluster_id: &str,
@@ -236,6 +240,37 @@ impl Clusters {
             .await
     }
 
+    /// Make sure a cluster is running, starting it if needed.
+    ///
+    /// A cluster that is pending or restarting is waited for, and one that
+    /// is terminating is started again once it has terminated.
+    pub async fn ensure_running(
+        &self,
+        cluster_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<ClusterDetails, Error> {
+        let start = Instant::now();
+        loop {
+            let details = self.get(cluster_id).await?;
+            match details.state {
+                Some(ClusterState::Running | ClusterState::Resizing) => return Ok(details),
+                Some(ClusterState::Terminated) => self.start(cluster_id).await?,
+                Some(ClusterState::Error) => {
+                    return Err(Error::Other(format!(
+                        "Cluster entered ERROR state: {}",
+                        details.state_message.unwrap_or_default()
+                    )))
+                }
+                _ => {}
+            }
+            if start.elapsed() > timeout {
+                return Err(Error::Timeout("Cluster operation timed out".into()));
+            }
+            self.client.runtime().sleep(poll_interval).await;
+        }
+    }
+
     async fn wait_for_state(
         &self,
         cluster_id: &str,
@@ -243,7 +278,7 @@ impl Clusters {
         poll_interval: Duration,
         timeout: Duration,
     ) -> Result<ClusterDetails, Error> {
-        let start = std::time::Instant::now();
+        let start = Instant::now();
         loop {
             let details = self.get(cluster_id).await?;
             if let Some(state) = details.state {
@@ -260,7 +295,7 @@ impl Clusters {
             if start.elapsed() > timeout {
                 return Err(Error::Timeout("Cluster operation timed out".into()));
             }
-            sleep(poll_interval).await;
+            self.client.runtime().sleep(poll_interval).await;
         }
     }
 }
@@ -0,0 +1,367 @@
+use crate::clusters::Clusters;
+use crate::command_execution::CommandExecution;
+use crate::types::{
+    CancelCommand, CommandOutput, CreateContext, DestroyContext, ExecuteCommand, Language,
+};
+use databricks_core::runtime::Instant;
+use databricks_core::{Client, Error};
+use std::time::Duration;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// An execution context on an interactive cluster, for running commands
+/// that share state, like cells of a notebook.
+///
+/// The context is created by the first [`ExecutionContext::run`]. Dropping
+/// an `ExecutionContext` destroys the context in the background; call
+/// [`ExecutionContext::destroy`] to wait for it instead. A context dropped
+/// outside of a runtime cannot be destroyed this way and is left for the
+/// cluster to clean up when it restarts.
+///
+/// ```no_run
+/// # async fn run(client: databricks_core::Client) -> Result<(), databricks_core::Error> {
+/// use databricks_compute::{CommandOutput, ExecutionContext, Language};
+/// use std::time::Duration;
+///
+/// let mut context = ExecutionContext::new(client, "cluster-id", Language::Python)
+///     .auto_start(Duration::from_secs(20 * 60));
+/// context.run("x = 40 + 2").await?;
+/// if let CommandOutput::Text(text) = context.run("print(x)").await? {
+///     println!("{}", text);
+/// }
+/// context.destroy().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ExecutionContext {
+    client: Client,
+    commands: CommandExecution,
+    cluster_id: String,
+    language: Language,
+    context_id: Option<String>,
+    auto_start: Option<Duration>,
+    poll_interval: Duration,
+    timeout: Duration,
+}
+
+impl ExecutionContext {
+    pub fn new(client: Client, cluster_id: impl Into<String>, language: Language) -> Self {
+        Self {
+            commands: CommandExecution::new(client.clone()),
+            client,
+            cluster_id: cluster_id.into(),
+            language,
+            context_id: None,
+            auto_start: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Start the cluster if it is not running, waiting up to `timeout` for
+    /// it, before creating the context.
+    pub fn auto_start(mut self, timeout: Duration) -> Self {
+        self.auto_start = Some(timeout);
+        self
+    }
+
+    /// Interval between status polls (default 1s).
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Maximum time to wait for the context to be created and for each
+    /// command (default 10 minutes). Commands that time out are cancelled.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn cluster_id(&self) -> &str {
+        &self.cluster_id
+    }
+
+    /// The context, once it has been created.
+    pub fn context_id(&self) -> Option<&str> {
+        self.context_id.as_deref()
+    }
+
+    /// Run a command and wait for its output.
+    ///
+    /// A command that raises returns [`CommandOutput::Error`]; a cancelled
+    /// command is an error.
+    pub async fn run(&mut self, command: impl Into<String>) -> Result<CommandOutput, Error> {
+        let context_id = self.context().await?;
+        let created = self
+            .commands
+            .execute(&ExecuteCommand {
+                cluster_id: Some(self.cluster_id.clone()),
+                context_id: Some(context_id.clone()),
+                language: Some(self.language.as_str().to_string()),
+                command: Some(command.into()),
+            })
+            .await?;
+        let command_id = created
+            .id
+            .ok_or_else(|| Error::Other("command execution returned no id".into()))?;
+
+        let start = Instant::now();
+        loop {
+            let status = self
+                .commands
+                .command_status(&self.cluster_id, &context_id, &command_id)
+                .await?;
+            match status.status.as_deref() {
+                Some("Finished") | Some("Error") => {
+                    return Ok(status
+                        .results
+                        .map(CommandOutput::from)
+                        .unwrap_or_else(|| CommandOutput::Text(String::new())));
+                }
+                Some("Cancelled") => {
+                    return Err(Error::Other(format!(
+                        "command {} was cancelled",
+                        command_id
+                    )))
+                }
+                _ => {}
+            }
+            if start.elapsed() > self.timeout {
+                let _ = self
+                    .commands
+                    .cancel(&CancelCommand {
+                        cluster_id: Some(self.cluster_id.clone()),
+                        context_id: Some(context_id.clone()),
+                        command_id: Some(command_id.clone()),
+                    })
+                    .await;
+                return Err(Error::Timeout("Command execution timed out".into()));
+            }
+            self.client.runtime().sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Destroy the context, if one was created.
+    pub async fn destroy(mut self) -> Result<(), Error> {
+        match self.context_id.take() {
+            Some(context_id) => {
+                self.commands
+                    .destroy(&DestroyContext {
+                        cluster_id: self.cluster_id.clone(),
+                        context_id,
+                    })
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    async fn context(&mut self) -> Result<String, Error> {
+        if let Some(context_id) = &self.context_id {
+            return Ok(context_id.clone());
+        }
+        if let Some(timeout) = self.auto_start {
+            Clusters::new(self.client.clone())
+                .ensure_running(&self.cluster_id, self.poll_interval, timeout)
+                .await?;
+        }
+
+        let created = self
+            .commands
+            .create(&CreateContext {
+                cluster_id: Some(self.cluster_id.clone()),
+                language: Some(self.language.as_str().to_string()),
+            })
+            .await?;
+        let context_id = created
+            .id
+            .ok_or_else(|| Error::Other("context creation returned no id".into()))?;
+        // Track the context before waiting so that it is destroyed even if it
+        // never becomes ready.
+        self.context_id = Some(context_id.clone());
+
+        let start = Instant::now();
+        loop {
+            let status = self
+                .commands
+                .context_status(&self.cluster_id, &context_id)
+                .await?;
+            match status.status.as_deref() {
+                Some("Running") => return Ok(context_id),
+                Some("Error") => {
+                    return Err(Error::Other(format!(
+                        "execution context {} failed to start",
+                        context_id
+                    )))
+                }
+                _ => {}
+            }
+            if start.elapsed() > self.timeout {
+                return Err(Error::Timeout(
+                    "Execution context creation timed out".into(),
+                ));
+            }
+            self.client.runtime().sleep(self.poll_interval).await;
+        }
+    }
+}
+
+impl Drop for ExecutionContext {
+    fn drop(&mut self) {
+        let Some(context_id) = self.context_id.take() else {
+            return;
+        };
+        let commands = CommandExecution::new(self.client.clone());
+        let request = DestroyContext {
+            cluster_id: self.cluster_id.clone(),
+            context_id,
+        };
+        self.client.runtime().spawn(Box::pin(async move {
+            let _ = commands.destroy(&request).await;
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ResultColumn;
+    use databricks_core::transport::mock::MockTransport;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_run_commands_in_context() {
+        let mock = MockTransport::new()
+            .on("GET", "/api/2.0/clusters/get", 200, json!({"cluster_id": "c1", "state": "TERMINATED"}))
+            .on("GET", "/api/2.0/clusters/get", 200, json!({"cluster_id": "c1", "state": "PENDING"}))
+            .on("GET", "/api/2.0/clusters/get", 200, json!({"cluster_id": "c1", "state": "RUNNING"}))
+            .on("POST", "/api/2.0/clusters/start", 200, json!({}))
+            .on("POST", "/api/1.2/contexts/create", 200, json!({"id": "ctx"}))
+            .on("GET", "/api/1.2/contexts/status", 200, json!({"id": "ctx", "status": "Pending"}))
+            .on("GET", "/api/1.2/contexts/status", 200, json!({"id": "ctx", "status": "Running"}))
+            .on("POST", "/api/1.2/commands/execute", 200, json!({"id": "cmd"}))
+            .on("GET", "/api/1.2/commands/status", 200, json!({"id": "cmd", "status": "Running"}))
+            .on("GET", "/api/1.2/commands/status", 200, json!({
+                "id": "cmd",
+                "status": "Finished",
+                "results": {
+                    "resultType": "table",
+                    "schema": [{"name": "id", "type": "\"long\""}],
+                    "data": [[1], [2]],
+                },
+            }))
+            .on("GET", "/api/1.2/commands/status", 200, json!({
+                "id": "cmd",
+                "status": "Error",
+                "results": {"resultType": "error", "summary": "NameError", "cause": "Traceback"},
+            }))
+            .on("POST", "/api/1.2/contexts/destroy", 200, json!({}));
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let mut context = ExecutionContext::new(client, "c1", Language::Sql)
+            .auto_start(Duration::from_secs(60))
+            .poll_interval(Duration::ZERO);
+        assert_eq!(
+            context.run("SELECT id FROM range(1, 3)").await.unwrap(),
+            CommandOutput::Table {
+                columns: vec![ResultColumn {
+                    name: "id".into(),
+                    type_name: Some("\"long\"".into()),
+                }],
+                rows: vec![vec![json!(1)], vec![json!(2)]],
+                truncated: false,
+            }
+        );
+        assert_eq!(context.context_id(), Some("ctx"));
+        let column = ResultColumn {
+            name: "id".into(),
+            type_name: Some("\"long\"".into()),
+        };
+        assert_eq!(column.data_type(), Some(json!("long")));
+        assert_eq!(
+            context.run("SELECT missing").await.unwrap(),
+            CommandOutput::Error {
+                summary: Some("NameError".into()),
+                cause: Some("Traceback".into()),
+            }
+        );
+        context.destroy().await.unwrap();
+
+        let requests: Vec<_> = mock
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == "POST")
+            .map(|r| r.path)
+            .collect();
+        assert_eq!(
+            requests,
+            [
+                "/api/2.0/clusters/start",
+                "/api/1.2/contexts/create",
+                "/api/1.2/commands/execute",
+                "/api/1.2/commands/execute",
+                "/api/1.2/contexts/destroy",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_drop_outside_runtime() {
+        let mock = MockTransport::new()
+            .on(
+                "POST",
+                "/api/1.2/contexts/create",
+                200,
+                json!({"id": "ctx"}),
+            )
+            .on(
+                "GET",
+                "/api/1.2/contexts/status",
+                200,
+                json!({"id": "ctx", "status": "Running"}),
+            )
+            .on(
+                "POST",
+                "/api/1.2/commands/execute",
+                200,
+                json!({"id": "cmd"}),
+            )
+            .on(
+                "GET",
+                "/api/1.2/commands/status",
+                200,
+                json!({
+                    "id": "cmd",
+                    "status": "Finished",
+                    "results": {"resultType": "text", "data": "42"},
+                }),
+            );
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let mut context =
+            ExecutionContext::new(client, "c1", Language::Python).poll_interval(Duration::ZERO);
+        let output = runtime.block_on(context.run("print(42)")).unwrap();
+        assert_eq!(output, CommandOutput::Text("42".into()));
+        drop(context);
+        assert!(!mock
+            .requests()
+            .iter()
+            .any(|r| r.path == "/api/1.2/contexts/destroy"));
+    }
+}
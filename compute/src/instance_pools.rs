@@ -58,6 +58,7 @@ impl InstancePools {
 
     // Permissions
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(
         &self,
         instance_pool_id: &str,
@@ -66,6 +67,7 @@ impl InstancePools {
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         instance_pool_id: &str,
@@ -77,6 +79,7 @@ impl InstancePools {
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         instance_pool_id: &str,
@@ -86,6 +89,7 @@ impl InstancePools {
         self.client.put(&path, request).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         instance_pool_id: &str,
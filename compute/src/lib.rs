@@ -1,6 +1,7 @@
 mod cluster_policies;
 mod clusters;
 mod command_execution;
+mod execution_context;
 mod global_init_scripts;
 mod instance_pools;
 mod instance_profiles;
@@ -11,6 +12,7 @@ mod types;
 pub use cluster_policies::ClusterPolicies;
 pub use clusters::Clusters;
 pub use command_execution::CommandExecution;
+pub use execution_context::ExecutionContext;
 pub use global_init_scripts::GlobalInitScripts;
 pub use instance_pools::InstancePools;
 pub use instance_profiles::InstanceProfiles;
@@ -455,6 +455,26 @@ pub struct AllClusterLibraryStatuses {
 // Command Execution types
 // ============================================================================
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Python,
+    Scala,
+    Sql,
+    R,
+}
+
+impl Language {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Language::Python => "python",
+            Language::Scala => "scala",
+            Language::Sql => "sql",
+            Language::R => "r",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct CancelCommand {
     #[serde(rename = "clusterId")]
@@ -527,6 +547,95 @@ pub struct Results {
     pub summary: Option<String>,
     #[serde(default)]
     pub truncated: Option<bool>,
+    /// Columns of a `table` result.
+    #[serde(default)]
+    pub schema: Vec<ResultColumn>,
+    #[serde(default, rename = "fileName")]
+    pub file_name: Option<String>,
+    #[serde(default, rename = "fileNames")]
+    pub file_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ResultColumn {
+    pub name: String,
+    /// Spark type, as a JSON-encoded string such as `"\"long\""`.
+    #[serde(default, rename = "type")]
+    pub type_name: Option<String>,
+}
+
+impl ResultColumn {
+    /// The decoded Spark type: a string such as `"long"` for simple types,
+    /// or an object for structs, arrays and maps.
+    pub fn data_type(&self) -> Option<serde_json::Value> {
+        let type_name = self.type_name.as_deref()?;
+        Some(
+            serde_json::from_str(type_name)
+                .unwrap_or_else(|_| serde_json::Value::String(type_name.to_string())),
+        )
+    }
+}
+
+/// The output of a command, decoded from [`Results`] by its `resultType`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandOutput {
+    Text(String),
+    Table {
+        columns: Vec<ResultColumn>,
+        rows: Vec<Vec<serde_json::Value>>,
+        truncated: bool,
+    },
+    /// Paths of images written by the command, on DBFS.
+    Images(Vec<String>),
+    /// The command raised an error; `cause` usually holds the stack trace.
+    Error {
+        summary: Option<String>,
+        cause: Option<String>,
+    },
+}
+
+impl CommandOutput {
+    pub fn is_error(&self) -> bool {
+        matches!(self, CommandOutput::Error { .. })
+    }
+}
+
+impl From<Results> for CommandOutput {
+    fn from(results: Results) -> Self {
+        let truncated = results.truncated.unwrap_or(false);
+        match results.result_type.as_deref() {
+            Some("error") => CommandOutput::Error {
+                summary: results.summary,
+                cause: results.cause,
+            },
+            Some("table") => CommandOutput::Table {
+                columns: results.schema,
+                rows: match results.data {
+                    Some(serde_json::Value::Array(rows)) => rows
+                        .into_iter()
+                        .map(|row| match row {
+                            serde_json::Value::Array(cells) => cells,
+                            cell => vec![cell],
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                },
+                truncated,
+            },
+            Some("image") | Some("images") => CommandOutput::Images(
+                results
+                    .file_name
+                    .into_iter()
+                    .chain(results.file_names)
+                    .collect(),
+            ),
+            _ => CommandOutput::Text(match results.data {
+                Some(serde_json::Value::String(text)) => text,
+                Some(serde_json::Value::Null) | None => String::new(),
+                Some(data) => data.to_string(),
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
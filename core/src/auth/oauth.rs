@@ -1,17 +1,25 @@
 use crate::error::Error;
+use crate::runtime::Instant;
+use crate::transport;
 use base64::Engine;
+use futures::lock::Mutex;
 use serde::Deserialize;
-use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// OAuth Machine-to-Machine (Client Credentials) authentication.
+///
+/// Tokens are requested through the default transport for the enabled
+/// features, or the one set with [`OAuthM2M::transport`].
 pub struct OAuthM2M {
     client_id: String,
     client_secret: String,
     token_endpoint: String,
-    cache: RwLock<Option<CachedToken>>,
+    transport: Option<Arc<dyn transport::Http>>,
+    cache: Mutex<Option<CachedToken>>,
 }
 
+#[derive(Clone)]
 struct CachedToken {
     access_token: String,
     expires_at: Instant,
@@ -38,60 +46,55 @@ impl OAuthM2M {
             client_id: client_id.into(),
             client_secret: client_secret.into(),
             token_endpoint: token_endpoint.into(),
-            cache: RwLock::new(None),
+            transport: crate::client::Builder::default_transport()
+                .ok()
+                .map(Arc::from),
+            cache: Mutex::new(None),
         }
     }
 
-    async fn fetch_token(&self) -> Result<CachedToken, Error> {
-        use http_body_util::{BodyExt, Full};
-        use hyper::body::Bytes;
-        use hyper::Request;
-        use hyper_util::client::legacy::Client;
-        use hyper_util::rt::TokioExecutor;
-
-        let https = hyper_rustls::HttpsConnectorBuilder::new()
-            .with_webpki_roots()
-            .https_or_http()
-            .enable_http1()
-            .build();
+    /// Set the transport used for token requests.
+    pub fn transport(mut self, transport: impl transport::Http + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
 
-        let client = Client::builder(TokioExecutor::new()).build(https);
+    async fn fetch_token(&self) -> Result<CachedToken, Error> {
+        let transport = self.transport.as_ref().ok_or_else(|| {
+            Error::Config(
+                "no transport for OAuth token requests; enable the \"hyper\", \"reqwest\" \
+                 or \"wasm\" feature or call OAuthM2M::transport()"
+                    .into(),
+            )
+        })?;
 
         let credentials = base64::engine::general_purpose::STANDARD
             .encode(format!("{}:{}", self.client_id, self.client_secret));
-
-        let body = "grant_type=client_credentials";
-
-        let req = Request::builder()
-            .method(hyper::Method::POST)
-            .uri(&self.token_endpoint)
-            .header("Authorization", format!("Basic {}", credentials))
-            .header("Content-Type", "application/x-www-form-urlencoded")
-            .body(Full::new(Bytes::from(body)))
-            .map_err(|e| Error::Other(format!("Failed to build OAuth request: {}", e)))?;
-
-        let response = client
-            .request(req)
-            .await
-            .map_err(|e| Error::Other(format!("OAuth token request failed: {}", e)))?;
-
-        let status = response.status();
-        let body_bytes = response
-            .into_body()
-            .collect()
-            .await
-            .map_err(|e| Error::Other(format!("Failed to read OAuth response: {}", e)))?
-            .to_bytes();
-
-        if !status.is_success() {
+        let headers = [
+            (
+                "Authorization".to_string(),
+                format!("Basic {}", credentials),
+            ),
+            (
+                "Content-Type".to_string(),
+                "application/x-www-form-urlencoded".to_string(),
+            ),
+        ];
+        let body = b"grant_type=client_credentials";
+
+        let response = transport
+            .request("POST", &self.token_endpoint, &headers, Some(body))
+            .await?;
+
+        if !(200..300).contains(&response.status) {
             return Err(Error::Other(format!(
                 "OAuth token request failed with HTTP {}: {}",
-                status,
-                String::from_utf8_lossy(&body_bytes)
+                response.status,
+                String::from_utf8_lossy(&response.body)
             )));
         }
 
-        let token_response: TokenResponse = serde_json::from_slice(&body_bytes)?;
+        let token_response: TokenResponse = serde_json::from_slice(&response.body)?;
 
         // Refresh 30 seconds before expiry
         let expires_at =
@@ -107,39 +110,105 @@ impl OAuthM2M {
 #[async_trait::async_trait]
 impl super::Provider for OAuthM2M {
     async fn authorize(&self) -> Result<Vec<(String, String)>, Error> {
-        // Check cache with read lock
+        // Hold the lock across the fetch so concurrent callers wait for one
+        // token request instead of each sending their own.
+        let mut cache = self.cache.lock().await;
+        let token = match cache
+            .as_ref()
+            .filter(|cached| Instant::now() < cached.expires_at)
         {
-            let cache = self.cache.read().await;
-            if let Some(cached) = cache.as_ref() {
-                if Instant::now() < cached.expires_at {
-                    return Ok(vec![(
-                        "Authorization".to_string(),
-                        format!("Bearer {}", cached.access_token),
-                    )]);
-                }
+            Some(cached) => cached.clone(),
+            None => {
+                let token = self.fetch_token().await?;
+                *cache = Some(token.clone());
+                token
             }
-        }
-
-        // Refresh token with write lock
-        let mut cache = self.cache.write().await;
-        // Double-check after acquiring write lock
-        if let Some(cached) = cache.as_ref() {
-            if Instant::now() < cached.expires_at {
-                return Ok(vec![(
-                    "Authorization".to_string(),
-                    format!("Bearer {}", cached.access_token),
-                )]);
-            }
-        }
-
-        let token = self.fetch_token().await?;
-        let header_value = format!("Bearer {}", token.access_token);
-        *cache = Some(token);
+        };
+        drop(cache);
 
-        Ok(vec![("Authorization".to_string(), header_value)])
+        Ok(vec![(
+            "Authorization".to_string(),
+            format!("Bearer {}", token.access_token),
+        )])
     }
 
     fn auth_type(&self) -> &str {
         "oauth-m2m"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Provider;
+    use crate::transport::mock::MockTransport;
+    use crate::transport::{Http, Response};
+
+    /// Yields before answering so concurrent callers interleave.
+    struct Yielding(MockTransport);
+
+    #[async_trait::async_trait]
+    impl Http for Yielding {
+        async fn request(
+            &self,
+            method: &str,
+            url: &str,
+            headers: &[(String, String)],
+            body: Option<&[u8]>,
+        ) -> Result<Response, crate::transport::Error> {
+            tokio::task::yield_now().await;
+            self.0.request(method, url, headers, body).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_is_fetched_once_and_cached() {
+        let mock = MockTransport::new().on(
+            "POST",
+            "/oidc/v1/token",
+            200,
+            serde_json::json!({"access_token": "abc", "expires_in": 3600}),
+        );
+        let oauth = OAuthM2M::new(
+            "id",
+            "secret",
+            "https://example.cloud.databricks.com/oidc/v1/token",
+        )
+        .transport(mock.clone());
+
+        for _ in 0..2 {
+            assert_eq!(
+                oauth.authorize().await.unwrap(),
+                [("Authorization".to_string(), "Bearer abc".to_string())]
+            );
+        }
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_callers_share_one_token_request() {
+        let mock = MockTransport::new().on(
+            "POST",
+            "/oidc/v1/token",
+            200,
+            serde_json::json!({"access_token": "abc", "expires_in": 3600}),
+        );
+        let oauth = OAuthM2M::new(
+            "id",
+            "secret",
+            "https://example.cloud.databricks.com/oidc/v1/token",
+        )
+        .transport(Yielding(mock.clone()));
+
+        let results = futures::future::join_all((0..8).map(|_| oauth.authorize())).await;
+        for headers in results {
+            assert_eq!(
+                headers.unwrap(),
+                [("Authorization".to_string(), "Bearer abc".to_string())]
+            );
+        }
+        assert_eq!(mock.requests().len(), 1);
+    }
+}
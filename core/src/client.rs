@@ -1,6 +1,7 @@
 use crate::auth;
 use crate::error::{ApiError, Error};
 use crate::retry;
+use crate::runtime;
 use crate::transport;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -11,6 +12,7 @@ struct Inner {
     host: String,
     credentials: Box<dyn auth::Provider>,
     retry_policy: retry::Policy,
+    runtime: Arc<dyn runtime::Runtime>,
 }
 
 /// Shared Databricks HTTP client, cheap to clone.
@@ -28,11 +30,18 @@ impl Client {
         &self.0.host
     }
 
+    /// The async runtime used for retries, polling and background work.
+    pub fn runtime(&self) -> &Arc<dyn runtime::Runtime> {
+        &self.0.runtime
+    }
+
     pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
         let path = path.to_string();
         self.0
             .retry_policy
-            .execute(|| self.request("GET", &path, Option::<&()>::None))
+            .execute(self.0.runtime.as_ref(), || {
+                self.request("GET", &path, Option::<&()>::None)
+            })
             .await
     }
 
@@ -45,7 +54,9 @@ impl Client {
         let path = path.to_string();
         self.0
             .retry_policy
-            .execute(|| self.request_raw("POST", &path, Some(&body_bytes)))
+            .execute(self.0.runtime.as_ref(), || {
+                self.request_raw("POST", &path, Some(&body_bytes))
+            })
             .await
     }
 
@@ -53,7 +64,9 @@ impl Client {
         let path = path.to_string();
         self.0
             .retry_policy
-            .execute(|| self.request("POST", &path, Option::<&()>::None))
+            .execute(self.0.runtime.as_ref(), || {
+                self.request("POST", &path, Option::<&()>::None)
+            })
             .await
     }
 
@@ -66,7 +79,9 @@ impl Client {
         let path = path.to_string();
         self.0
             .retry_policy
-            .execute(|| self.request_raw("PUT", &path, Some(&body_bytes)))
+            .execute(self.0.runtime.as_ref(), || {
+                self.request_raw("PUT", &path, Some(&body_bytes))
+            })
             .await
     }
 
@@ -79,7 +94,9 @@ impl Client {
         let path = path.to_string();
         self.0
             .retry_policy
-            .execute(|| self.request_raw("PATCH", &path, Some(&body_bytes)))
+            .execute(self.0.runtime.as_ref(), || {
+                self.request_raw("PATCH", &path, Some(&body_bytes))
+            })
             .await
     }
 
@@ -87,7 +104,9 @@ impl Client {
         let path = path.to_string();
         self.0
             .retry_policy
-            .execute(|| self.request("DELETE", &path, Option::<&()>::None))
+            .execute(self.0.runtime.as_ref(), || {
+                self.request("DELETE", &path, Option::<&()>::None)
+            })
             .await
     }
 
@@ -95,7 +114,7 @@ impl Client {
         let path = path.to_string();
         self.0
             .retry_policy
-            .execute(|| self.do_delete_empty(&path))
+            .execute(self.0.runtime.as_ref(), || self.do_delete_empty(&path))
             .await
     }
 
@@ -125,7 +144,7 @@ impl Client {
     ) -> Result<T, Error> {
         let query_string: String = query
             .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
+            .map(|(k, v)| format!("{}={}", encode_query(k), encode_query(v)))
             .collect::<Vec<_>>()
             .join("&");
 
@@ -137,7 +156,7 @@ impl Client {
 
         self.0
             .retry_policy
-            .execute(|| self.do_get_raw(&full_path))
+            .execute(self.0.runtime.as_ref(), || self.do_get_raw(&full_path))
             .await
     }
 
@@ -164,7 +183,7 @@ impl Client {
         let path = path.to_string();
         self.0
             .retry_policy
-            .execute(|| self.do_get_bytes(&path))
+            .execute(self.0.runtime.as_ref(), || self.do_get_bytes(&path))
             .await
     }
 
@@ -185,6 +204,64 @@ impl Client {
         Ok(resp.body)
     }
 
+    /// Upload a raw request body, such as file contents.
+    pub async fn put_bytes(&self, path: &str, data: &[u8]) -> Result<(), Error> {
+        let path = path.to_string();
+        self.0
+            .retry_policy
+            .execute(self.0.runtime.as_ref(), || self.do_put_bytes(&path, data))
+            .await
+    }
+
+    async fn do_put_bytes(&self, path: &str, data: &[u8]) -> Result<(), Error> {
+        let url = format!("{}{}", self.0.host.trim_end_matches('/'), path);
+        let mut headers = self.0.credentials.authorize().await?;
+        headers.push(("Content-Type".into(), "application/octet-stream".into()));
+
+        let resp = self
+            .0
+            .transport
+            .request("PUT", &url, &headers, Some(data))
+            .await?;
+
+        if resp.status < 200 || resp.status >= 300 {
+            return Err(parse_error_response(resp.status, &resp.body));
+        }
+
+        Ok(())
+    }
+
+    /// Fetch a URL outside the workspace, such as a presigned cloud storage
+    /// link, without attaching Databricks credentials.
+    ///
+    /// Only `headers` are sent; retries follow the client's retry policy.
+    pub async fn get_external(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+    ) -> Result<Vec<u8>, Error> {
+        self.0
+            .retry_policy
+            .execute(self.0.runtime.as_ref(), || {
+                self.do_get_external(url, headers)
+            })
+            .await
+    }
+
+    async fn do_get_external(
+        &self,
+        url: &str,
+        headers: &[(String, String)],
+    ) -> Result<Vec<u8>, Error> {
+        let resp = self.0.transport.request("GET", url, headers, None).await?;
+
+        if resp.status < 200 || resp.status >= 300 {
+            return Err(parse_error_response(resp.status, &resp.body));
+        }
+
+        Ok(resp.body)
+    }
+
     async fn request<B: Serialize, T: DeserializeOwned>(
         &self,
         method: &str,
@@ -224,6 +301,21 @@ impl Client {
     }
 }
 
+/// Percent-encode a query string key or value, keeping only unreserved
+/// characters as-is.
+fn encode_query(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
 fn parse_error_response(status: u16, body: &[u8]) -> Error {
     let retry_after_secs = if status == 429 { Some(1) } else { None };
 
@@ -254,6 +346,7 @@ pub struct Builder {
     credentials: Option<Box<dyn auth::Provider>>,
     retry_policy: Option<retry::Policy>,
     transport: Option<Box<dyn transport::Http>>,
+    runtime: Option<Arc<dyn runtime::Runtime>>,
 }
 
 impl Builder {
@@ -282,12 +375,24 @@ impl Builder {
     /// Set a custom HTTP transport.
     ///
     /// If not set, defaults to [`transport::hyper::HyperTransport`] when the
-    /// `hyper` feature is enabled.
+    /// `hyper` feature is enabled, or `transport::reqwest::ReqwestTransport`
+    /// with the `reqwest` feature, or `transport::fetch::FetchTransport` with
+    /// the `wasm` feature.
     pub fn transport(mut self, transport: impl transport::Http + 'static) -> Self {
         self.transport = Some(Box::new(transport));
         self
     }
 
+    /// Set a custom async runtime.
+    ///
+    /// If not set, defaults to [`runtime::tokio::TokioRuntime`] when the
+    /// `tokio` feature is enabled, or `runtime::wasm::WasmRuntime` with the
+    /// `wasm` feature.
+    pub fn runtime(mut self, runtime: impl runtime::Runtime + 'static) -> Self {
+        self.runtime = Some(Arc::new(runtime));
+        self
+    }
+
     /// Build from a resolved `config::Config`, using the credential chain.
     pub fn from_config(config: crate::config::Config) -> Result<Client, Error> {
         let host = config
@@ -321,23 +426,87 @@ impl Builder {
             None => Self::default_transport()?,
         };
 
+        let runtime: Arc<dyn runtime::Runtime> = match self.runtime {
+            Some(r) => r,
+            None => Self::default_runtime()?,
+        };
+
         Ok(Client(Arc::new(Inner {
             transport,
             host,
             credentials,
             retry_policy: self.retry_policy.unwrap_or_default(),
+            runtime,
         })))
     }
 
     #[cfg(feature = "hyper")]
-    fn default_transport() -> Result<Box<dyn transport::Http>, Error> {
+    pub(crate) fn default_transport() -> Result<Box<dyn transport::Http>, Error> {
         Ok(Box::new(transport::hyper::HyperTransport::new()))
     }
 
-    #[cfg(not(feature = "hyper"))]
-    fn default_transport() -> Result<Box<dyn transport::Http>, Error> {
+    #[cfg(all(not(feature = "hyper"), feature = "reqwest"))]
+    pub(crate) fn default_transport() -> Result<Box<dyn transport::Http>, Error> {
+        Ok(Box::new(transport::reqwest::ReqwestTransport::new()))
+    }
+
+    #[cfg(all(not(any(feature = "hyper", feature = "reqwest")), feature = "wasm"))]
+    pub(crate) fn default_transport() -> Result<Box<dyn transport::Http>, Error> {
+        Ok(Box::new(transport::fetch::FetchTransport::new()))
+    }
+
+    #[cfg(not(any(feature = "hyper", feature = "reqwest", feature = "wasm")))]
+    pub(crate) fn default_transport() -> Result<Box<dyn transport::Http>, Error> {
+        Err(Error::Config(
+            "no transport provided; enable the \"hyper\", \"reqwest\" or \"wasm\" feature or call .transport()"
+                .into(),
+        ))
+    }
+
+    #[cfg(feature = "tokio")]
+    fn default_runtime() -> Result<Arc<dyn runtime::Runtime>, Error> {
+        Ok(Arc::new(runtime::tokio::TokioRuntime))
+    }
+
+    #[cfg(all(not(feature = "tokio"), feature = "wasm"))]
+    fn default_runtime() -> Result<Arc<dyn runtime::Runtime>, Error> {
+        Ok(Arc::new(runtime::wasm::WasmRuntime))
+    }
+
+    #[cfg(not(any(feature = "tokio", feature = "wasm")))]
+    fn default_runtime() -> Result<Arc<dyn runtime::Runtime>, Error> {
         Err(Error::Config(
-            "no transport provided; enable the \"hyper\" feature or call .transport()".into(),
+            "no runtime provided; enable the \"tokio\" or \"wasm\" feature or call .runtime()"
+                .into(),
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::mock::MockTransport;
+
+    #[tokio::test]
+    async fn test_get_with_query_encodes_values() {
+        let mock = MockTransport::new().on("GET", "/api/2.0/items", 200, serde_json::json!({}));
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let _: serde_json::Value = client
+            .get_with_query(
+                "/api/2.0/items",
+                &[("page_token", "a+b/c=="), ("filter_by.name", "x y&z")],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            mock.requests()[0].path,
+            "/api/2.0/items?page_token=a%2Bb%2Fc%3D%3D&filter_by.name=x%20y%26z"
+        );
+    }
+}
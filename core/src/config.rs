@@ -34,7 +34,7 @@ impl Config {
             std::env::var("DATABRICKS_CONFIG_FILE")
                 .ok()
                 .map(PathBuf::from)
-                .or_else(|| dirs::home_dir().map(|h| h.join(".databrickscfg")))
+                .or_else(default_config_file)
         });
 
         let file_values = config_file
@@ -129,6 +129,17 @@ impl Builder {
     }
 }
 
+/// `~/.databrickscfg`, when the platform has a home directory.
+#[cfg(not(target_arch = "wasm32"))]
+fn default_config_file() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".databrickscfg"))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn default_config_file() -> Option<PathBuf> {
+    None
+}
+
 /// Parse a `.databrickscfg` INI file, returning values for the given profile.
 fn parse_cfg_file(path: &PathBuf, profile: &str) -> Option<HashMap<String, String>> {
     let contents = std::fs::read_to_string(path).ok()?;
@@ -4,8 +4,8 @@ pub mod config;
 pub mod error;
 pub mod paginator;
 pub mod retry;
+pub mod runtime;
 pub mod transport;
-#[cfg(feature = "hyper")]
 pub mod wait;
 
 pub use client::{Builder, Client};
@@ -1,4 +1,5 @@
 use crate::error::Error;
+use crate::runtime::{Instant, Runtime};
 use std::future::Future;
 use std::time::Duration;
 
@@ -22,43 +23,33 @@ impl Policy {
     /// Execute the given async operation with retries on retryable errors.
     ///
     /// Uses exponential backoff with jitter: `min(2^attempt, 30) + random(50ms..750ms)`.
-    /// Respects `retry_after_secs` from 429 responses.
-    ///
-    /// Without the `hyper` feature, executes a single attempt (no retries).
-    pub async fn execute<F, Fut, T>(&self, mut op: F) -> Result<T, Error>
+    /// Respects `retry_after_secs` from 429 responses. Delays are slept on
+    /// `runtime`.
+    pub async fn execute<F, Fut, T>(&self, runtime: &dyn Runtime, mut op: F) -> Result<T, Error>
     where
         F: FnMut() -> Fut,
         Fut: Future<Output = Result<T, Error>>,
     {
-        #[cfg(not(feature = "hyper"))]
-        {
-            op().await
-        }
-
-        #[cfg(feature = "hyper")]
-        {
-            let start = std::time::Instant::now();
+        let start = Instant::now();
 
-            for attempt in 0..=self.max_retries {
-                match op().await {
-                    Ok(val) => return Ok(val),
-                    Err(err) if attempt < self.max_retries && err.is_retryable() => {
-                        let delay = backoff_delay(attempt, err.retry_after_secs());
-                        if start.elapsed() + delay > self.timeout {
-                            return Err(err);
-                        }
-                        tokio::time::sleep(delay).await;
+        for attempt in 0..=self.max_retries {
+            match op().await {
+                Ok(val) => return Ok(val),
+                Err(err) if attempt < self.max_retries && err.is_retryable() => {
+                    let delay = backoff_delay(attempt, err.retry_after_secs());
+                    if start.elapsed() + delay > self.timeout {
+                        return Err(err);
                     }
-                    Err(err) => return Err(err),
+                    runtime.sleep(delay).await;
                 }
+                Err(err) => return Err(err),
             }
-
-            unreachable!()
         }
+
+        unreachable!()
     }
 }
 
-#[cfg(feature = "hyper")]
 fn backoff_delay(attempt: u32, retry_after_secs: Option<u64>) -> Duration {
     if let Some(secs) = retry_after_secs {
         return Duration::from_secs(secs);
@@ -68,3 +59,54 @@ fn backoff_delay(attempt: u32, retry_after_secs: Option<u64>) -> Duration {
     let jitter_ms = rand::Rng::gen_range(&mut rand::thread_rng(), 50..750);
     Duration::from_secs(base_secs) + Duration::from_millis(jitter_ms)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::BoxFuture;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    /// Runtime that records sleeps instead of waiting.
+    #[derive(Default)]
+    struct Recording {
+        sleeps: AtomicU32,
+    }
+
+    impl Runtime for Recording {
+        fn sleep(&self, _duration: Duration) -> BoxFuture<()> {
+            self.sleeps.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async {})
+        }
+
+        fn spawn(&self, _future: BoxFuture<()>) {}
+    }
+
+    #[tokio::test]
+    async fn test_retries_on_custom_runtime() {
+        let runtime = Recording::default();
+        let attempts = Arc::new(AtomicU32::new(0));
+
+        let result = Policy::default()
+            .execute(&runtime, || {
+                let attempts = attempts.clone();
+                async move {
+                    if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                        Err(Error::Api {
+                            code: crate::error::Code::Unavailable,
+                            status: 503,
+                            message: "busy".into(),
+                            retry_after_secs: Some(0),
+                        })
+                    } else {
+                        Ok(42)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(runtime.sleeps.load(Ordering::SeqCst), 2);
+    }
+}
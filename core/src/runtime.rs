@@ -0,0 +1,66 @@
+#[cfg(feature = "tokio")]
+pub mod tokio;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use crate::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+/// Monotonic clock used for timeouts; `std::time::Instant` panics on
+/// `wasm32-unknown-unknown`.
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+pub use web_time::Instant;
+
+/// Wall clock, for the same reason as [`Instant`].
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+pub use web_time::{SystemTime, UNIX_EPOCH};
+
+/// A boxed, sendable future as returned by [`Runtime`] methods.
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send + 'static>>;
+
+/// Async runtime hooks.
+///
+/// The SDK only needs timers (for retries and polling) and the ability to run
+/// background work (for concurrent downloads). Implement this trait to use the
+/// client with an executor other than tokio.
+pub trait Runtime: Send + Sync {
+    /// Complete after `duration` has elapsed.
+    fn sleep(&self, duration: Duration) -> BoxFuture<()>;
+
+    /// Run `future` in the background, detached from the caller.
+    ///
+    /// Called from `Drop` impls, so it must not panic; a runtime that cannot
+    /// run the future here may drop it instead.
+    fn spawn(&self, future: BoxFuture<()>);
+
+    /// Run `task`, which may block (e.g. on file I/O), without stalling the
+    /// executor. The returned future completes when `task` has finished.
+    ///
+    /// The default runs `task` on the calling thread.
+    fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>) -> BoxFuture<()> {
+        task();
+        Box::pin(std::future::ready(()))
+    }
+}
+
+/// Run blocking `f` through [`Runtime::spawn_blocking`] and return its result.
+pub async fn blocking<T, F>(runtime: &dyn Runtime, f: F) -> Result<T, Error>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = futures::channel::oneshot::channel();
+    runtime
+        .spawn_blocking(Box::new(move || {
+            let _ = tx.send(f());
+        }))
+        .await;
+    rx.await
+        .map_err(|_| Error::Other("blocking task panicked".into()))
+}
@@ -0,0 +1,39 @@
+use super::{BoxFuture, Runtime};
+use std::time::Duration;
+
+/// Runtime backed by the ambient tokio runtime.
+///
+/// Must be used from within a tokio runtime context.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioRuntime;
+
+impl Runtime for TokioRuntime {
+    fn sleep(&self, duration: Duration) -> BoxFuture<()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+
+    /// Spawns onto the current tokio runtime. Outside of one, the future is
+    /// dropped without running.
+    fn spawn(&self, future: BoxFuture<()>) {
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(future);
+        }
+    }
+
+    /// Runs `task` on tokio's blocking thread pool, or on the calling thread
+    /// outside of a tokio runtime.
+    fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send>) -> BoxFuture<()> {
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                let join = handle.spawn_blocking(task);
+                Box::pin(async move {
+                    let _ = join.await;
+                })
+            }
+            Err(_) => {
+                task();
+                Box::pin(std::future::ready(()))
+            }
+        }
+    }
+}
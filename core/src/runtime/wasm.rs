@@ -0,0 +1,40 @@
+use super::{BoxFuture, Runtime};
+use futures_channel::oneshot;
+use js_sys::{Function, Promise};
+use std::time::Duration;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &Function, timeout: i32) -> JsValue;
+}
+
+/// Runtime for `wasm32-unknown-unknown`, using `setTimeout` for timers and
+/// the local JS executor for background work.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WasmRuntime;
+
+impl Runtime for WasmRuntime {
+    fn sleep(&self, duration: Duration) -> BoxFuture<()> {
+        let millis = duration.as_millis().min(i32::MAX as u128) as i32;
+        let (tx, rx) = oneshot::channel();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let promise = Promise::new(&mut |resolve, _reject| {
+                set_timeout(&resolve, millis);
+            });
+            let _ = JsFuture::from(promise).await;
+            let _ = tx.send(());
+        });
+
+        Box::pin(async move {
+            let _ = rx.await;
+        })
+    }
+
+    fn spawn(&self, future: BoxFuture<()>) {
+        wasm_bindgen_futures::spawn_local(future);
+    }
+}
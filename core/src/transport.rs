@@ -1,5 +1,11 @@
+#[cfg(feature = "wasm")]
+pub mod fetch;
 #[cfg(feature = "hyper")]
 pub mod hyper;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+#[cfg(feature = "reqwest")]
+pub mod reqwest;
 
 /// Raw HTTP transport.
 ///
@@ -0,0 +1,101 @@
+use super::{Error, Response};
+use futures_channel::oneshot;
+use js_sys::{Promise, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    // Bound as a free function so it resolves on both `Window` and worker
+    // global scopes (including Cloudflare Workers).
+    #[wasm_bindgen(js_name = fetch)]
+    fn fetch_with_request(input: &web_sys::Request) -> Promise;
+}
+
+/// HTTP transport backed by the JavaScript `fetch` API.
+///
+/// For `wasm32-unknown-unknown` targets such as browsers and edge runtimes.
+/// JS futures are not `Send`, so each request runs on the local executor
+/// and its result is handed back over a channel.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FetchTransport;
+
+impl FetchTransport {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl super::Http for FetchTransport {
+    async fn request(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(String, String)],
+        body: Option<&[u8]>,
+    ) -> Result<Response, Error> {
+        let method = method.to_string();
+        let url = url.to_string();
+        let headers = headers.to_vec();
+        let body = body.map(|b| b.to_vec());
+
+        let (tx, rx) = oneshot::channel();
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = tx.send(send(&method, &url, &headers, body.as_deref()).await);
+        });
+
+        rx.await
+            .map_err(|_| Error::Other("fetch request was dropped".into()))?
+    }
+}
+
+async fn send(
+    method: &str,
+    url: &str,
+    headers: &[(String, String)],
+    body: Option<&[u8]>,
+) -> Result<Response, Error> {
+    let init = web_sys::RequestInit::new();
+    init.set_method(method);
+    if let Some(b) = body {
+        init.set_body(&Uint8Array::from(b).into());
+    }
+
+    let request = web_sys::Request::new_with_str_and_init(url, &init)
+        .map_err(|e| Error::InvalidUrl(js_error(&e)))?;
+    for (name, value) in headers {
+        request
+            .headers()
+            .set(name, value)
+            .map_err(|e| Error::Other(js_error(&e)))?;
+    }
+
+    let response: web_sys::Response = JsFuture::from(fetch_with_request(&request))
+        .await
+        .map_err(|e| Error::Connection(js_error(&e)))?
+        .dyn_into()
+        .map_err(|e| Error::Other(js_error(&e)))?;
+
+    let status = response.status();
+    let buffer = response
+        .array_buffer()
+        .map_err(|e| Error::Other(js_error(&e)))?;
+    let buffer = JsFuture::from(buffer)
+        .await
+        .map_err(|e| Error::Connection(js_error(&e)))?;
+
+    Ok(Response {
+        status,
+        body: Uint8Array::new(&buffer).to_vec(),
+    })
+}
+
+fn js_error(value: &JsValue) -> String {
+    value
+        .dyn_ref::<js_sys::Error>()
+        .map(|e| String::from(e.message()))
+        .or_else(|| value.as_string())
+        .unwrap_or_else(|| format!("{:?}", value))
+}
@@ -0,0 +1,155 @@
+use super::{Error, Response};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// In-memory transport that answers requests from canned responses.
+///
+/// Routes match on method and path; the host and query string are ignored.
+/// Responses registered for the same route are returned in order, and the
+/// last one repeats, so a route polled until completion can be given a
+/// series of states. Unmatched requests get a `404`.
+///
+/// Requires the `mock` feature; enable it from `[dev-dependencies]`.
+///
+/// Clones share routes and the request log, so keep a clone to inspect
+/// what a [`crate::Client`] sent:
+///
+/// ```
+/// use databricks_core::transport::mock::MockTransport;
+/// use databricks_core::Client;
+///
+/// let mock = MockTransport::new().on(
+///     "GET",
+///     "/api/2.0/sql/warehouses",
+///     200,
+///     serde_json::json!({"warehouses": []}),
+/// );
+/// let client = Client::builder()
+///     .host("https://example.cloud.databricks.com")
+///     .token("token")
+///     .transport(mock.clone())
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Default)]
+struct Inner {
+    routes: Vec<Route>,
+    requests: Vec<MockRequest>,
+}
+
+struct Route {
+    method: String,
+    path: String,
+    responses: VecDeque<(u16, Vec<u8>)>,
+}
+
+/// A request received by a [`MockTransport`].
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub method: String,
+    /// Path and query string, without the host.
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<serde_json::Value>,
+}
+
+impl MockRequest {
+    /// The value of header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Respond to `method` requests for `path` with `status` and a JSON body.
+    pub fn on(self, method: &str, path: &str, status: u16, body: serde_json::Value) -> Self {
+        self.on_raw(method, path, status, body.to_string().into_bytes())
+    }
+
+    /// Like [`MockTransport::on`], with a raw body.
+    pub fn on_raw(self, method: &str, path: &str, status: u16, body: Vec<u8>) -> Self {
+        {
+            let mut inner = self.inner.lock().expect("mock transport lock");
+            match inner
+                .routes
+                .iter_mut()
+                .find(|r| r.method == method && r.path == path)
+            {
+                Some(route) => route.responses.push_back((status, body)),
+                None => inner.routes.push(Route {
+                    method: method.to_string(),
+                    path: path.to_string(),
+                    responses: VecDeque::from([(status, body)]),
+                }),
+            }
+        }
+        self
+    }
+
+    /// Requests received so far, oldest first.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.inner
+            .lock()
+            .expect("mock transport lock")
+            .requests
+            .clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl super::Http for MockTransport {
+    async fn request(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(String, String)],
+        body: Option<&[u8]>,
+    ) -> Result<Response, Error> {
+        let path = url
+            .find("://")
+            .and_then(|scheme| url[scheme + 3..].find('/').map(|i| &url[scheme + 3 + i..]))
+            .unwrap_or(url);
+        let route_path = path.split('?').next().unwrap_or(path);
+
+        let mut inner = self.inner.lock().expect("mock transport lock");
+        inner.requests.push(MockRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            headers: headers.to_vec(),
+            body: body.and_then(|b| serde_json::from_slice(b).ok()),
+        });
+
+        let route = inner
+            .routes
+            .iter_mut()
+            .find(|r| r.method == method && r.path == route_path);
+        let (status, body) = match route {
+            Some(route) if route.responses.len() > 1 => {
+                route.responses.pop_front().expect("non-empty responses")
+            }
+            Some(route) => route.responses[0].clone(),
+            None => (
+                404,
+                serde_json::json!({
+                    "error_code": "NOT_FOUND",
+                    "message": format!("no mock response for {} {}", method, route_path),
+                })
+                .to_string()
+                .into_bytes(),
+            ),
+        };
+        Ok(Response { status, body })
+    }
+}
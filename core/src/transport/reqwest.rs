@@ -0,0 +1,69 @@
+use super::{Error, Response};
+
+/// HTTP transport backed by reqwest + rustls.
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Wrap a preconfigured reqwest client (proxies, timeouts, custom roots).
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Default for ReqwestTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl super::Http for ReqwestTransport {
+    async fn request(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(String, String)],
+        body: Option<&[u8]>,
+    ) -> Result<Response, Error> {
+        let method = reqwest::Method::from_bytes(method.as_bytes())
+            .map_err(|e| Error::Other(e.to_string()))?;
+
+        let mut builder = self.client.request(method, url);
+
+        for (name, value) in headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        if let Some(b) = body {
+            builder = builder.body(b.to_vec());
+        }
+
+        let response = builder.send().await?;
+        let status = response.status().as_u16();
+        let body = response.bytes().await?.to_vec();
+
+        Ok(Response { status, body })
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            Error::Timeout(e.to_string())
+        } else if e.is_builder() {
+            Error::InvalidUrl(e.to_string())
+        } else if e.is_connect() || e.is_request() || e.is_body() {
+            Error::Connection(e.to_string())
+        } else {
+            Error::Other(e.to_string())
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::error::Error;
+use crate::runtime::{Instant, Runtime};
 use std::future::Future;
 use std::pin::Pin;
-use std::time::{Duration, Instant};
+use std::sync::Arc;
+use std::time::Duration;
 
 type PollFn<T> =
     Box<dyn Fn() -> Pin<Box<dyn Future<Output = Result<Poll<T>, Error>> + Send>> + Send + Sync>;
@@ -19,10 +21,19 @@ pub struct Wait<T> {
     poll_fn: PollFn<T>,
     interval: Duration,
     timeout: Duration,
+    runtime: Arc<dyn Runtime>,
 }
 
 impl<T: Send + 'static> Wait<T> {
-    pub fn new<F, Fut>(poll_fn: F, interval: Duration, timeout: Duration) -> Self
+    /// Create a waiter that sleeps between polls on `runtime`.
+    ///
+    /// Use [`crate::Client::runtime`] to share the client's runtime.
+    pub fn new<F, Fut>(
+        runtime: Arc<dyn Runtime>,
+        poll_fn: F,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Self
     where
         F: Fn() -> Fut + Send + Sync + 'static,
         Fut: Future<Output = Result<Poll<T>, Error>> + Send + 'static,
@@ -31,6 +42,7 @@ impl<T: Send + 'static> Wait<T> {
             poll_fn: Box::new(move || Box::pin(poll_fn())),
             interval,
             timeout,
+            runtime,
         }
     }
 
@@ -57,7 +69,7 @@ impl<T: Send + 'static> Wait<T> {
                 return Err(Error::Timeout("operation timed out".into()));
             }
 
-            tokio::time::sleep(self.interval).await;
+            self.runtime.sleep(self.interval).await;
         }
     }
 }
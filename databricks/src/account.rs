@@ -29,6 +29,14 @@ impl Client {
         Ok(Self { inner, account_id })
     }
 
+    /// Wrap an existing core client, e.g. one built with a custom transport.
+    pub fn from_core_client(client: CoreClient, account_id: impl Into<String>) -> Self {
+        Self {
+            inner: client,
+            account_id: account_id.into(),
+        }
+    }
+
     /// The account ID this client is configured for.
     pub fn account_id(&self) -> &str {
         &self.account_id
@@ -0,0 +1,1084 @@
+//! Workspace-wide permissions audit.
+//!
+//! Answers "who can do what" by enumerating workspace objects (compute, jobs,
+//! SQL and ML objects, and the notebooks, directories and files of the
+//! workspace tree), Unity Catalog securables and secret scopes, reading their
+//! ACLs, and expanding group grants (including nested groups) to the users
+//! and service principals they reach.
+//!
+//! ```no_run
+//! # async fn run(ws: &databricks::workspace::Client) -> Result<(), databricks::Error> {
+//! use databricks::audit::{Auditor, OverPrivileged};
+//!
+//! let report = Auditor::new(ws).run().await?;
+//! std::fs::write("acl.csv", report.to_csv()).unwrap();
+//!
+//! for entry in &report.over_privileged(&OverPrivileged::default()).entries {
+//!     println!("{} has {} on {}", entry.granted_to, entry.permission, entry.object);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use databricks_core::error::Error;
+use databricks_core::Client as CoreClient;
+use databricks_iam::{ObjectRef, Principal};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// An object covered by the audit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum Object {
+    /// A workspace object governed by the Permissions API.
+    Workspace(ObjectRef),
+    Catalog(String),
+    Schema(String),
+    Table(String),
+    SecretScope(String),
+}
+
+impl Object {
+    pub fn kind(&self) -> &str {
+        match self {
+            Object::Workspace(object) => object.object_type(),
+            Object::Catalog(_) => "catalog",
+            Object::Schema(_) => "schema",
+            Object::Table(_) => "table",
+            Object::SecretScope(_) => "secret-scope",
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        match self {
+            Object::Workspace(object) => object.object_id(),
+            Object::Catalog(name)
+            | Object::Schema(name)
+            | Object::Table(name)
+            | Object::SecretScope(name) => name,
+        }
+    }
+}
+
+impl std::fmt::Display for Object {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.kind(), self.id())
+    }
+}
+
+/// A principal in an audit [`Entry`].
+///
+/// Unity Catalog and secret ACLs name principals without saying what kind
+/// they are. Names that match no user, group or service principal of the
+/// workspace, such as account-level groups, are `Unknown`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(tag = "type", content = "name", rename_all = "snake_case")]
+pub enum AuditPrincipal {
+    User(String),
+    Group(String),
+    ServicePrincipal(String),
+    Unknown(String),
+}
+
+impl AuditPrincipal {
+    pub fn name(&self) -> &str {
+        match self {
+            AuditPrincipal::User(name)
+            | AuditPrincipal::Group(name)
+            | AuditPrincipal::ServicePrincipal(name)
+            | AuditPrincipal::Unknown(name) => name,
+        }
+    }
+}
+
+impl From<Principal> for AuditPrincipal {
+    fn from(principal: Principal) -> Self {
+        match principal {
+            Principal::User(name) => AuditPrincipal::User(name),
+            Principal::Group(name) => AuditPrincipal::Group(name),
+            Principal::ServicePrincipal(name) => AuditPrincipal::ServicePrincipal(name),
+        }
+    }
+}
+
+impl std::fmt::Display for AuditPrincipal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditPrincipal::User(name) => write!(f, "user:{}", name),
+            AuditPrincipal::Group(name) => write!(f, "group:{}", name),
+            AuditPrincipal::ServicePrincipal(name) => write!(f, "service_principal:{}", name),
+            AuditPrincipal::Unknown(name) => write!(f, "unknown:{}", name),
+        }
+    }
+}
+
+/// One principal × object × permission row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Entry {
+    /// The user or service principal that effectively holds the permission.
+    /// Equals `granted_to` for direct grants and for groups whose members
+    /// could not be resolved.
+    pub principal: AuditPrincipal,
+    /// The principal named in the ACL.
+    pub granted_to: AuditPrincipal,
+    pub object: Object,
+    /// Display name of the object, when it differs from its ID.
+    pub object_name: Option<String>,
+    pub permission: String,
+    /// Whether the grant is inherited from a parent object.
+    pub inherited: bool,
+}
+
+/// An object whose ACL could not be read.
+#[derive(Debug, Clone, Serialize)]
+pub struct Skipped {
+    /// The object, or object type when listing failed.
+    pub object: String,
+    pub error: String,
+}
+
+/// Result of an audit run.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Report {
+    pub entries: Vec<Entry>,
+    pub skipped: Vec<Skipped>,
+}
+
+impl Report {
+    /// Entries matching an over-privilege pattern.
+    pub fn over_privileged(&self, pattern: &OverPrivileged) -> Report {
+        Report {
+            entries: self
+                .entries
+                .iter()
+                .filter(|e| pattern.matches(e))
+                .cloned()
+                .collect(),
+            skipped: Vec::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Render entries as CSV with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "principal_type,principal,granted_to_type,granted_to,object_type,object_id,object_name,permission,inherited\n",
+        );
+        for e in &self.entries {
+            let fields = [
+                principal_type(&e.principal),
+                e.principal.name(),
+                principal_type(&e.granted_to),
+                e.granted_to.name(),
+                e.object.kind(),
+                e.object.id(),
+                e.object_name.as_deref().unwrap_or(""),
+                &e.permission,
+                if e.inherited { "true" } else { "false" },
+            ];
+            let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn principal_type(principal: &AuditPrincipal) -> &'static str {
+    match principal {
+        AuditPrincipal::User(_) => "user",
+        AuditPrincipal::Group(_) => "group",
+        AuditPrincipal::ServicePrincipal(_) => "service_principal",
+        AuditPrincipal::Unknown(_) => "unknown",
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Pattern for grants that give broad groups powerful permissions.
+///
+/// Defaults to the `users` and `account users` groups holding any of
+/// `CAN_MANAGE`, `IS_OWNER`, `ALL_PRIVILEGES` or `MANAGE`.
+#[derive(Debug, Clone)]
+pub struct OverPrivileged {
+    groups: BTreeSet<String>,
+    permissions: BTreeSet<String>,
+}
+
+impl Default for OverPrivileged {
+    fn default() -> Self {
+        Self {
+            groups: ["users", "account users"].map(String::from).into(),
+            permissions: ["CAN_MANAGE", "IS_OWNER", "ALL_PRIVILEGES", "MANAGE"]
+                .map(String::from)
+                .into(),
+        }
+    }
+}
+
+impl OverPrivileged {
+    /// A pattern with no groups or permissions; add them with the builder methods.
+    pub fn empty() -> Self {
+        Self {
+            groups: BTreeSet::new(),
+            permissions: BTreeSet::new(),
+        }
+    }
+
+    pub fn group(mut self, name: impl Into<String>) -> Self {
+        self.groups.insert(name.into());
+        self
+    }
+
+    pub fn permission(mut self, level: impl Into<String>) -> Self {
+        self.permissions.insert(level.into());
+        self
+    }
+
+    pub fn matches(&self, entry: &Entry) -> bool {
+        matches!(&entry.granted_to, AuditPrincipal::Group(g) if self.groups.contains(g))
+            && self.permissions.contains(&entry.permission)
+    }
+}
+
+/// Runs a permissions audit over a workspace.
+pub struct Auditor {
+    client: CoreClient,
+    workspace_objects: bool,
+    workspace_tree: bool,
+    unity_catalog: bool,
+    tables: bool,
+    secrets: bool,
+}
+
+impl Auditor {
+    /// Audit workspace objects, catalogs, schemas and secret scopes.
+    /// Tables are off by default as they can be numerous.
+    pub fn new(workspace: &crate::workspace::Client) -> Self {
+        Self {
+            client: workspace.core_client().clone(),
+            workspace_objects: true,
+            workspace_tree: true,
+            unity_catalog: true,
+            tables: false,
+            secrets: true,
+        }
+    }
+
+    pub fn workspace_objects(mut self, enabled: bool) -> Self {
+        self.workspace_objects = enabled;
+        self
+    }
+
+    /// Include notebooks, directories and files found by walking the
+    /// workspace tree from `/`. Only applies when workspace objects are
+    /// audited.
+    pub fn workspace_tree(mut self, enabled: bool) -> Self {
+        self.workspace_tree = enabled;
+        self
+    }
+
+    pub fn unity_catalog(mut self, enabled: bool) -> Self {
+        self.unity_catalog = enabled;
+        self
+    }
+
+    /// Include individual tables in the Unity Catalog audit.
+    pub fn tables(mut self, enabled: bool) -> Self {
+        self.tables = enabled;
+        self
+    }
+
+    pub fn secrets(mut self, enabled: bool) -> Self {
+        self.secrets = enabled;
+        self
+    }
+
+    /// Run the audit.
+    ///
+    /// Fails only if the principal directory cannot be loaded; objects whose
+    /// ACLs cannot be read are reported in [`Report::skipped`].
+    pub async fn run(&self) -> Result<Report, Error> {
+        let directory = Directory::load(&self.client).await?;
+        let mut report = Report::default();
+
+        if self.workspace_objects {
+            self.audit_workspace_objects(&directory, &mut report).await;
+        }
+        if self.unity_catalog {
+            self.audit_unity_catalog(&directory, &mut report).await;
+        }
+        if self.secrets {
+            self.audit_secrets(&directory, &mut report).await;
+        }
+
+        Ok(report)
+    }
+
+    async fn audit_workspace_objects(&self, directory: &Directory, report: &mut Report) {
+        let mut objects: Vec<(ObjectRef, Option<String>)> =
+            vec![(ObjectRef::Tokens, None), (ObjectRef::Passwords, None)];
+
+        let c = self.client.clone();
+        match databricks_compute::Clusters::new(c.clone()).list().await {
+            Ok(items) => objects.extend(
+                items
+                    .into_iter()
+                    .filter_map(|i| Some((ObjectRef::Cluster(i.cluster_id?), i.cluster_name))),
+            ),
+            Err(e) => skip(report, "clusters", e),
+        }
+        match databricks_compute::ClusterPolicies::new(c.clone())
+            .list()
+            .await
+        {
+            Ok(items) => objects.extend(
+                items
+                    .into_iter()
+                    .filter_map(|i| Some((ObjectRef::ClusterPolicy(i.policy_id?), i.name))),
+            ),
+            Err(e) => skip(report, "cluster-policies", e),
+        }
+        match databricks_compute::InstancePools::new(c.clone())
+            .list()
+            .await
+        {
+            Ok(items) => objects.extend(items.into_iter().filter_map(|i| {
+                Some((
+                    ObjectRef::InstancePool(i.instance_pool_id?),
+                    i.instance_pool_name,
+                ))
+            })),
+            Err(e) => skip(report, "instance-pools", e),
+        }
+        match list_jobs(&c).await {
+            Ok(items) => objects.extend(items),
+            Err(e) => skip(report, "jobs", e),
+        }
+        match list_pipelines(&c).await {
+            Ok(items) => objects.extend(items),
+            Err(e) => skip(report, "pipelines", e),
+        }
+        match databricks_sql::Warehouses::new(c.clone()).list().await {
+            Ok(items) => objects.extend(
+                items
+                    .into_iter()
+                    .map(|i| (ObjectRef::Warehouse(i.id), Some(i.name))),
+            ),
+            Err(e) => skip(report, "warehouses", e),
+        }
+        match list_repos(&c).await {
+            Ok(items) => objects.extend(items),
+            Err(e) => skip(report, "repos", e),
+        }
+        match databricks_serving::ServingEndpoints::new(c.clone())
+            .list()
+            .await
+        {
+            Ok(items) => objects.extend(
+                items
+                    .into_iter()
+                    .filter_map(|i| Some((ObjectRef::ServingEndpoint(i.id?), i.name))),
+            ),
+            Err(e) => skip(report, "serving-endpoints", e),
+        }
+        match databricks_apps::Apps::new(c.clone()).list().await {
+            Ok(items) => objects.extend(items.into_iter().map(|i| (ObjectRef::App(i.name), None))),
+            Err(e) => skip(report, "apps", e),
+        }
+        match databricks_sql::Queries::new(c.clone())
+            .list_all()
+            .collect_all()
+            .await
+        {
+            Ok(items) => objects.extend(
+                items
+                    .into_iter()
+                    .filter_map(|i| Some((ObjectRef::Query(i.id?), i.display_name))),
+            ),
+            Err(e) => skip(report, "queries", e),
+        }
+        match databricks_sql::Alerts::new(c.clone())
+            .list_all()
+            .collect_all()
+            .await
+        {
+            Ok(items) => objects.extend(
+                items
+                    .into_iter()
+                    .filter_map(|i| Some((ObjectRef::Alert(i.id?), i.display_name))),
+            ),
+            Err(e) => skip(report, "alerts", e),
+        }
+        match list_dashboards(&c).await {
+            Ok(items) => objects.extend(items),
+            Err(e) => skip(report, "dashboards", e),
+        }
+        match list_experiments(&c).await {
+            Ok(items) => objects.extend(items),
+            Err(e) => skip(report, "experiments", e),
+        }
+        match list_registered_models(&c, report).await {
+            Ok(items) => objects.extend(items),
+            Err(e) => skip(report, "registered-models", e),
+        }
+        if self.workspace_tree {
+            objects.extend(list_workspace_tree(&c, report).await);
+        }
+
+        let permissions = databricks_iam::ObjectPermissions::new(c);
+        for (object, name) in objects {
+            match permissions.get(&object).await {
+                Ok(acl) => {
+                    for grant in acl.grants {
+                        directory.push_entries(
+                            report,
+                            &AuditPrincipal::from(grant.principal),
+                            Object::Workspace(object.clone()),
+                            name.clone(),
+                            grant.level.as_str(),
+                            grant.inherited,
+                        );
+                    }
+                }
+                Err(e) => skip(report, &object.to_string(), e),
+            }
+        }
+    }
+
+    async fn audit_unity_catalog(&self, directory: &Directory, report: &mut Report) {
+        let c = self.client.clone();
+        let mut objects = Vec::new();
+
+        let catalogs = match databricks_catalog::Catalogs::new(c.clone()).list().await {
+            Ok(items) => items.into_iter().filter_map(|i| i.name).collect(),
+            Err(e) => {
+                skip(report, "catalogs", e);
+                Vec::new()
+            }
+        };
+        for catalog in catalogs {
+            match databricks_catalog::Schemas::new(c.clone())
+                .list(&catalog)
+                .await
+            {
+                Ok(schemas) => {
+                    for schema in schemas.into_iter().filter_map(|s| s.name) {
+                        if self.tables {
+                            match databricks_catalog::Tables::new(c.clone())
+                                .list(&catalog, &schema)
+                                .await
+                            {
+                                Ok(tables) => objects.extend(
+                                    tables
+                                        .into_iter()
+                                        .filter_map(|t| Some(Object::Table(t.full_name?))),
+                                ),
+                                Err(e) => skip(report, &format!("{}.{}", catalog, schema), e),
+                            }
+                        }
+                        objects.push(Object::Schema(format!("{}.{}", catalog, schema)));
+                    }
+                }
+                Err(e) => skip(report, &format!("catalog/{}", catalog), e),
+            }
+            objects.push(Object::Catalog(catalog));
+        }
+
+        let grants = databricks_catalog::Grants::new(c);
+        for object in objects {
+            match grants.get_effective(object.kind(), object.id()).await {
+                Ok(list) => {
+                    for assignment in list.privilege_assignments {
+                        let Some(name) = assignment.principal else {
+                            continue;
+                        };
+                        let principal = directory.classify(&name);
+                        for privilege in assignment.privileges {
+                            let Some(level) = privilege.privilege else {
+                                continue;
+                            };
+                            directory.push_entries(
+                                report,
+                                &principal,
+                                object.clone(),
+                                None,
+                                &level,
+                                privilege.inherited_from_name.is_some(),
+                            );
+                        }
+                    }
+                }
+                Err(e) => skip(report, &object.to_string(), e),
+            }
+        }
+    }
+
+    async fn audit_secrets(&self, directory: &Directory, report: &mut Report) {
+        let secrets = databricks_workspace_api::Secrets::new(self.client.clone());
+        let scopes = match secrets.list_scopes().await {
+            Ok(scopes) => scopes,
+            Err(e) => return skip(report, "secret-scopes", e),
+        };
+        for scope in scopes {
+            let object = Object::SecretScope(scope.name);
+            match secrets.list_acls(object.id()).await {
+                Ok(acls) => {
+                    for acl in acls {
+                        let principal = directory.classify(&acl.principal);
+                        directory.push_entries(
+                            report,
+                            &principal,
+                            object.clone(),
+                            None,
+                            &acl.permission,
+                            false,
+                        );
+                    }
+                }
+                Err(e) => skip(report, &object.to_string(), e),
+            }
+        }
+    }
+}
+
+fn skip(report: &mut Report, object: &str, error: Error) {
+    report.skipped.push(Skipped {
+        object: object.to_string(),
+        error: error.to_string(),
+    });
+}
+
+async fn list_pipelines(client: &CoreClient) -> Result<Vec<(ObjectRef, Option<String>)>, Error> {
+    let pipelines = databricks_pipelines::Pipelines::new(client.clone());
+    let mut objects = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let page = pipelines
+            .list(None, Some(100), page_token.as_deref())
+            .await?;
+        objects.extend(
+            page.statuses
+                .into_iter()
+                .filter_map(|p| Some((ObjectRef::Pipeline(p.pipeline_id?), p.name))),
+        );
+        page_token = page.next_page_token;
+        if page_token.is_none() {
+            return Ok(objects);
+        }
+    }
+}
+
+async fn list_jobs(client: &CoreClient) -> Result<Vec<(ObjectRef, Option<String>)>, Error> {
+    let jobs = databricks_jobs::Jobs::new(client.clone());
+    let mut objects = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let page = jobs.list_page(page_token.as_deref()).await?;
+        objects.extend(page.jobs.into_iter().filter_map(|j| {
+            Some((
+                ObjectRef::Job(j.job_id?.to_string()),
+                j.settings.and_then(|s| s.name),
+            ))
+        }));
+        page_token = page.next_page_token.filter(|_| page.has_more);
+        if page_token.is_none() {
+            return Ok(objects);
+        }
+    }
+}
+
+async fn list_repos(client: &CoreClient) -> Result<Vec<(ObjectRef, Option<String>)>, Error> {
+    let repos = databricks_workspace_api::Repos::new(client.clone());
+    let mut objects = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let page = repos.list_page(page_token.as_deref()).await?;
+        objects.extend(
+            page.repos
+                .into_iter()
+                .filter_map(|r| Some((ObjectRef::Repo(r.id?.to_string()), r.path))),
+        );
+        page_token = page.next_page_token;
+        if page_token.is_none() {
+            return Ok(objects);
+        }
+    }
+}
+
+async fn list_dashboards(client: &CoreClient) -> Result<Vec<(ObjectRef, Option<String>)>, Error> {
+    let dashboards = databricks_dashboards::Dashboards::new(client.clone());
+    let mut objects = Vec::new();
+    let mut request = databricks_dashboards::ListDashboardsRequest {
+        page_size: Some(100),
+        ..Default::default()
+    };
+    loop {
+        let page = dashboards.list(&request).await?;
+        objects.extend(
+            page.dashboards
+                .into_iter()
+                .filter_map(|d| Some((ObjectRef::Dashboard(d.dashboard_id?), d.display_name))),
+        );
+        request.page_token = page.next_page_token.filter(|t| !t.is_empty());
+        if request.page_token.is_none() {
+            return Ok(objects);
+        }
+    }
+}
+
+async fn list_experiments(client: &CoreClient) -> Result<Vec<(ObjectRef, Option<String>)>, Error> {
+    let experiments = databricks_ml::Experiments::new(client.clone());
+    let mut objects = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let page = experiments
+            .search(&databricks_ml::SearchExperimentsRequest {
+                filter: None,
+                max_results: Some(1000),
+                order_by: None,
+                page_token: page_token.take(),
+                view_type: None,
+            })
+            .await?;
+        objects.extend(
+            page.experiments
+                .into_iter()
+                .filter_map(|e| Some((ObjectRef::Experiment(e.experiment_id?), e.name))),
+        );
+        page_token = page.next_page_token.filter(|t| !t.is_empty());
+        if page_token.is_none() {
+            return Ok(objects);
+        }
+    }
+}
+
+/// Workspace registry models. Their permissions are keyed by an ID that only
+/// the Databricks variant of `get` returns, so each model is looked up.
+async fn list_registered_models(
+    client: &CoreClient,
+    report: &mut Report,
+) -> Result<Vec<(ObjectRef, Option<String>)>, Error> {
+    let registry = databricks_ml::ModelRegistry::new(client.clone());
+    let mut names = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let page = registry
+            .list_models(Some(1000), page_token.as_deref())
+            .await?;
+        names.extend(page.registered_models.into_iter().filter_map(|m| m.name));
+        page_token = page.next_page_token.filter(|t| !t.is_empty());
+        if page_token.is_none() {
+            break;
+        }
+    }
+
+    let mut objects = Vec::new();
+    for name in names {
+        match registry.get_model(&name).await {
+            Ok(model) => {
+                if let Some(id) = model.registered_model_databricks.and_then(|m| m.id) {
+                    objects.push((ObjectRef::RegisteredModel(id), Some(name)));
+                }
+            }
+            Err(e) => skip(report, &format!("registered-models/{}", name), e),
+        }
+    }
+    Ok(objects)
+}
+
+/// Notebooks, directories and files under `/`, excluding repos (listed
+/// separately) and libraries (which have no ACLs). Directories that cannot
+/// be listed are reported as skipped and not descended into.
+async fn list_workspace_tree(
+    client: &CoreClient,
+    report: &mut Report,
+) -> Vec<(ObjectRef, Option<String>)> {
+    use databricks_workspace_api::ObjectType;
+
+    let notebooks = databricks_workspace_api::Notebooks::new(client.clone());
+    let mut objects = Vec::new();
+    let mut pending = vec!["/".to_string()];
+    while let Some(dir) = pending.pop() {
+        let items = match notebooks.list(&dir).await {
+            Ok(items) => items,
+            Err(e) => {
+                skip(report, &format!("workspace:{}", dir), e);
+                continue;
+            }
+        };
+        for item in items {
+            let (Some(kind), Some(id), Some(path)) = (item.object_type, item.object_id, item.path)
+            else {
+                continue;
+            };
+            let object = match kind {
+                ObjectType::Notebook => ObjectRef::Notebook(id.to_string()),
+                ObjectType::File => ObjectRef::File(id.to_string()),
+                ObjectType::Directory => {
+                    pending.push(path.clone());
+                    ObjectRef::Directory(id.to_string())
+                }
+                ObjectType::Repo | ObjectType::Library => continue,
+            };
+            objects.push((object, Some(path)));
+        }
+    }
+    objects
+}
+
+/// Page size for SCIM listings.
+const SCIM_PAGE_SIZE: i64 = 100;
+
+/// Collect every page of a SCIM listing. `list_page` takes a 1-based start
+/// index and page size, and returns the page and the total result count.
+async fn list_scim<T, F, Fut>(mut list_page: F) -> Result<Vec<T>, Error>
+where
+    F: FnMut(i64, i64) -> Fut,
+    Fut: std::future::Future<Output = Result<(Vec<T>, Option<i64>), Error>>,
+{
+    let mut items = Vec::new();
+    loop {
+        let (page, total) = list_page(items.len() as i64 + 1, SCIM_PAGE_SIZE).await?;
+        let done = page.is_empty();
+        items.extend(page);
+        if done || total.is_none_or(|t| items.len() as i64 >= t) {
+            return Ok(items);
+        }
+    }
+}
+
+/// Users, service principals and group membership of a workspace.
+#[derive(Debug, Default)]
+struct Directory {
+    /// Principal for each SCIM ID.
+    by_id: HashMap<String, Principal>,
+    /// Member SCIM IDs for each group name.
+    members: HashMap<String, Vec<String>>,
+    /// Service principal application IDs.
+    application_ids: HashSet<String>,
+    /// User names.
+    user_names: HashSet<String>,
+}
+
+impl Directory {
+    async fn load(client: &CoreClient) -> Result<Self, Error> {
+        let mut directory = Directory::default();
+
+        let users = &databricks_iam::Users::new(client.clone());
+        let users = list_scim(|start, count| async move {
+            let page = users.list_page(start, count).await?;
+            Ok((page.resources, page.total_results))
+        })
+        .await?;
+        for user in users {
+            if let (Some(id), Some(name)) = (user.id, user.user_name) {
+                directory.user_names.insert(name.clone());
+                directory.by_id.insert(id, Principal::User(name));
+            }
+        }
+        let service_principals = &databricks_iam::ServicePrincipals::new(client.clone());
+        let service_principals = list_scim(|start, count| async move {
+            let page = service_principals.list_page(start, count).await?;
+            Ok((page.resources, page.total_results))
+        })
+        .await?;
+        for sp in service_principals {
+            if let (Some(id), Some(app_id)) = (sp.id, sp.application_id) {
+                directory.application_ids.insert(app_id.clone());
+                directory
+                    .by_id
+                    .insert(id, Principal::ServicePrincipal(app_id));
+            }
+        }
+        let groups = &databricks_iam::Groups::new(client.clone());
+        let groups = list_scim(|start, count| async move {
+            let page = groups.list_page(start, count).await?;
+            Ok((page.resources, page.total_results))
+        })
+        .await?;
+        for group in groups {
+            if let (Some(id), Some(name)) = (group.id, group.display_name) {
+                let members = group
+                    .members
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|m| m.value)
+                    .collect();
+                directory.members.insert(name.clone(), members);
+                directory.by_id.insert(id, Principal::Group(name));
+            }
+        }
+
+        Ok(directory)
+    }
+
+    /// Map a bare principal name (as used by Unity Catalog and secret ACLs)
+    /// to a typed principal.
+    fn classify(&self, name: &str) -> AuditPrincipal {
+        if self.members.contains_key(name) {
+            AuditPrincipal::Group(name.to_string())
+        } else if self.application_ids.contains(name) {
+            AuditPrincipal::ServicePrincipal(name.to_string())
+        } else if self.user_names.contains(name) {
+            AuditPrincipal::User(name.to_string())
+        } else {
+            AuditPrincipal::Unknown(name.to_string())
+        }
+    }
+
+    /// Users and service principals reachable from `principal` through
+    /// (possibly nested) group membership.
+    fn expand(&self, principal: &AuditPrincipal) -> BTreeSet<AuditPrincipal> {
+        let mut found = BTreeSet::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![principal.clone()];
+
+        while let Some(p) = stack.pop() {
+            match &p {
+                AuditPrincipal::Group(name) => {
+                    if !visited.insert(name.clone()) {
+                        continue;
+                    }
+                    for id in self.members.get(name).into_iter().flatten() {
+                        if let Some(member) = self.by_id.get(id) {
+                            stack.push(AuditPrincipal::from(member.clone()));
+                        }
+                    }
+                }
+                _ => {
+                    found.insert(p);
+                }
+            }
+        }
+
+        found
+    }
+
+    fn push_entries(
+        &self,
+        report: &mut Report,
+        granted_to: &AuditPrincipal,
+        object: Object,
+        object_name: Option<String>,
+        permission: &str,
+        inherited: bool,
+    ) {
+        let mut effective = self.expand(granted_to);
+        if effective.is_empty() {
+            effective.insert(granted_to.clone());
+        }
+        for principal in effective {
+            report.entries.push(Entry {
+                principal,
+                granted_to: granted_to.clone(),
+                object: object.clone(),
+                object_name: object_name.clone(),
+                permission: permission.to_string(),
+                inherited,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_groups_expand_to_members() {
+        let mut directory = Directory::default();
+        directory
+            .by_id
+            .insert("1".into(), Principal::User("a@example.com".into()));
+        directory
+            .by_id
+            .insert("2".into(), Principal::ServicePrincipal("app-1".into()));
+        directory
+            .by_id
+            .insert("10".into(), Principal::Group("data".into()));
+        directory
+            .by_id
+            .insert("11".into(), Principal::Group("eng".into()));
+        directory
+            .members
+            .insert("data".into(), vec!["1".into(), "11".into()]);
+        // Cycle back to `data` must not loop forever.
+        directory
+            .members
+            .insert("eng".into(), vec!["2".into(), "10".into()]);
+
+        let expanded = directory.expand(&AuditPrincipal::Group("data".into()));
+        assert_eq!(
+            expanded.into_iter().collect::<Vec<_>>(),
+            vec![
+                AuditPrincipal::User("a@example.com".into()),
+                AuditPrincipal::ServicePrincipal("app-1".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_and_over_privileged() {
+        let directory = Directory::default();
+        let mut report = Report::default();
+        directory.push_entries(
+            &mut report,
+            &AuditPrincipal::Group("users".into()),
+            Object::Workspace(ObjectRef::Cluster("c1".into())),
+            Some("shared, \"big\"".into()),
+            "CAN_MANAGE",
+            false,
+        );
+
+        assert_eq!(
+            report.to_csv().lines().nth(1).unwrap(),
+            "group,users,group,users,clusters,c1,\"shared, \"\"big\"\"\",CAN_MANAGE,false"
+        );
+        assert_eq!(
+            report
+                .over_privileged(&OverPrivileged::default())
+                .entries
+                .len(),
+            1
+        );
+        assert!(report
+            .over_privileged(&OverPrivileged::empty().group("admins"))
+            .entries
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_pages_directory_and_classify_unknown() {
+        use databricks_core::transport::mock::MockTransport;
+        use serde_json::json;
+
+        let users = "/api/2.0/preview/scim/v2/Users";
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                users,
+                200,
+                json!({"Resources": [{"id": "1", "userName": "a@example.com"}], "totalResults": 2}),
+            )
+            .on(
+                "GET",
+                users,
+                200,
+                json!({"Resources": [{"id": "2", "userName": "b@example.com"}], "totalResults": 2}),
+            )
+            .on(
+                "GET",
+                "/api/2.0/preview/scim/v2/ServicePrincipals",
+                200,
+                json!({"Resources": [], "totalResults": 0}),
+            )
+            .on(
+                "GET",
+                "/api/2.0/preview/scim/v2/Groups",
+                200,
+                json!({
+                    "Resources": [{"id": "10", "displayName": "data", "members": [{"value": "2"}]}],
+                    "totalResults": 1,
+                }),
+            );
+        let client = CoreClient::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let directory = Directory::load(&client).await.unwrap();
+        assert_eq!(
+            directory.classify("b@example.com"),
+            AuditPrincipal::User("b@example.com".into())
+        );
+        assert_eq!(
+            directory.expand(&directory.classify("data")),
+            BTreeSet::from([AuditPrincipal::User("b@example.com".into())])
+        );
+        assert_eq!(
+            directory.classify("account users"),
+            AuditPrincipal::Unknown("account users".into())
+        );
+
+        let listed: Vec<_> = mock
+            .requests()
+            .into_iter()
+            .map(|r| r.path)
+            .filter(|p| p.starts_with(users))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                format!("{}?startIndex=1&count=100", users),
+                format!("{}?startIndex=2&count=100", users),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_workspace_tree_walks_directories() {
+        use databricks_core::transport::mock::MockTransport;
+        use serde_json::json;
+
+        let list = "/api/2.0/workspace/list";
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                list,
+                200,
+                json!({"objects": [
+                    {"object_type": "DIRECTORY", "object_id": 1, "path": "/Shared"},
+                    {"object_type": "REPO", "object_id": 2, "path": "/Repos/a/b"},
+                    {"object_type": "NOTEBOOK", "object_id": 3, "path": "/nb"},
+                ]}),
+            )
+            .on(
+                "GET",
+                list,
+                200,
+                json!({"objects": [
+                    {"object_type": "FILE", "object_id": 4, "path": "/Shared/data.csv"},
+                ]}),
+            );
+        let client = CoreClient::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let mut report = Report::default();
+        let objects = list_workspace_tree(&client, &mut report).await;
+        assert_eq!(
+            objects,
+            [
+                (ObjectRef::Directory("1".into()), Some("/Shared".into())),
+                (ObjectRef::Notebook("3".into()), Some("/nb".into())),
+                (ObjectRef::File("4".into()), Some("/Shared/data.csv".into())),
+            ]
+        );
+        assert!(report.skipped.is_empty());
+        assert_eq!(
+            mock.requests()
+                .into_iter()
+                .map(|r| r.path)
+                .collect::<Vec<_>>(),
+            [
+                format!("{}?path=%2F", list),
+                format!("{}?path=%2FShared", list),
+            ]
+        );
+    }
+}
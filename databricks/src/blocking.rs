@@ -0,0 +1,170 @@
+//! Synchronous facade over the async clients.
+//!
+//! Each blocking client owns a private tokio runtime and drives the async
+//! services on it, so callers never need their own runtime:
+//!
+//! ```no_run
+//! use databricks::blocking::workspace;
+//!
+//! let ws = workspace::Client::new()?;
+//! for wh in ws.warehouses().call(|w| w.list())? {
+//!     println!("{} ({:?})", wh.name, wh.state);
+//! }
+//! # Ok::<(), databricks::Error>(())
+//! ```
+//!
+//! Blocking clients must not be used from within an async context; calling
+//! them from a tokio task panics.
+
+pub mod account;
+pub mod workspace;
+
+use databricks_core::error::Error;
+use databricks_core::paginator::Paginator;
+use databricks_core::wait::Wait;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Build the runtime shared by a blocking client and its services.
+fn runtime() -> Result<Arc<Runtime>, Error> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| Error::Other(format!("failed to start blocking runtime: {}", e)))?;
+    Ok(Arc::new(runtime))
+}
+
+/// A service handle whose async methods are driven to completion on the
+/// owning client's runtime.
+pub struct Service<S> {
+    inner: S,
+    runtime: Arc<Runtime>,
+}
+
+impl<S> Service<S> {
+    fn new(inner: S, runtime: Arc<Runtime>) -> Self {
+        Self { inner, runtime }
+    }
+
+    /// Run an async method of the wrapped service and block until it completes.
+    ///
+    /// ```no_run
+    /// # let ws = databricks::blocking::workspace::Client::new()?;
+    /// let cluster = ws.clusters().call(|c| c.get("0123-456789-abcdefgh"))?;
+    /// # Ok::<(), databricks::Error>(())
+    /// ```
+    pub fn call<'a, F, Fut, T>(&'a self, f: F) -> T
+    where
+        F: FnOnce(&'a S) -> Fut,
+        Fut: Future<Output = T> + 'a,
+    {
+        self.runtime.block_on(f(&self.inner))
+    }
+
+    /// Access the wrapped async service.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Block until a long-running operation completes.
+    pub fn wait<T: Send + 'static>(&self, wait: Wait<T>) -> Result<T, Error> {
+        self.runtime.block_on(wait.result())
+    }
+
+    /// Iterate a paginated list, fetching pages on demand.
+    pub fn paginate<T: Send + 'static>(&self, paginator: Paginator<T>) -> Pages<T> {
+        Pages::new(paginator, self.runtime.clone())
+    }
+}
+
+/// Blocking iterator over a [`Paginator`].
+///
+/// Iteration stops after the first error, which is yielded as the last item.
+pub struct Pages<T> {
+    paginator: Paginator<T>,
+    runtime: Arc<Runtime>,
+}
+
+impl<T: Send + 'static> Pages<T> {
+    fn new(paginator: Paginator<T>, runtime: Arc<Runtime>) -> Self {
+        Self { paginator, runtime }
+    }
+
+    /// Collect all remaining items into a Vec.
+    pub fn collect_all(mut self) -> Result<Vec<T>, Error> {
+        self.runtime.block_on(self.paginator.collect_all())
+    }
+}
+
+impl<T: Send + 'static> Iterator for Pages<T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.paginator.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use databricks_core::transport::mock::MockTransport;
+    use databricks_core::Client as CoreClient;
+    use serde_json::json;
+
+    fn core_client(mock: &MockTransport) -> CoreClient {
+        CoreClient::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_workspace_call_and_paginate() {
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                "/api/2.0/sql/warehouses",
+                200,
+                json!({"warehouses": [{"id": "wh", "name": "Shared", "state": "RUNNING"}]}),
+            )
+            .on(
+                "GET",
+                "/api/2.0/sql/queries",
+                200,
+                json!({"results": [{"id": "q1"}], "next_page_token": "p2"}),
+            )
+            .on(
+                "GET",
+                "/api/2.0/sql/queries",
+                200,
+                json!({"results": [{"id": "q2"}]}),
+            );
+        let ws = super::workspace::Client::from_core_client(core_client(&mock)).unwrap();
+
+        let warehouses = ws.warehouses().call(|w| w.list()).unwrap();
+        assert_eq!(warehouses[0].id, "wh");
+
+        let queries = ws.queries();
+        let ids: Vec<_> = queries
+            .paginate(queries.inner().list_all())
+            .map(|q| q.unwrap().id)
+            .collect();
+        assert_eq!(ids, [Some("q1".to_string()), Some("q2".to_string())]);
+    }
+
+    #[test]
+    fn test_account_call() {
+        let mock = MockTransport::new().on(
+            "GET",
+            "/api/2.0/accounts/acct/workspaces",
+            200,
+            json!([{"workspace_id": 1, "workspace_name": "prod"}]),
+        );
+        let account = super::account::Client::from_core_client(core_client(&mock), "acct").unwrap();
+
+        let workspaces = account.workspaces().call(|w| w.list()).unwrap();
+        assert_eq!(workspaces[0].workspace_name.as_deref(), Some("prod"));
+    }
+}
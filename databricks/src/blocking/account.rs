@@ -0,0 +1,146 @@
+use super::{runtime, Pages, Service};
+use databricks_core::config;
+use databricks_core::error::Error;
+use databricks_core::paginator::Paginator;
+use databricks_core::wait::Wait;
+use databricks_core::Client as CoreClient;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Blocking counterpart of [`crate::account::Client`].
+///
+/// Every accessor returns a [`Service`] bound to this client's runtime.
+pub struct Client {
+    inner: crate::account::Client,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Create an account client from default configuration.
+    pub fn new() -> Result<Self, Error> {
+        let config = config::Builder::default().build()?;
+        Self::with_config(config)
+    }
+
+    /// Create an account client from an explicit configuration.
+    pub fn with_config(config: config::Config) -> Result<Self, Error> {
+        let inner = crate::account::Client::with_config(config)?;
+        Ok(Self {
+            inner,
+            runtime: runtime()?,
+        })
+    }
+
+    /// Wrap an existing core client, e.g. one built with a custom transport.
+    pub fn from_core_client(
+        client: CoreClient,
+        account_id: impl Into<String>,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            inner: crate::account::Client::from_core_client(client, account_id),
+            runtime: runtime()?,
+        })
+    }
+
+    /// The account ID this client is configured for.
+    pub fn account_id(&self) -> &str {
+        self.inner.account_id()
+    }
+
+    /// Access the underlying core client (for advanced/custom usage).
+    pub fn core_client(&self) -> &CoreClient {
+        self.inner.core_client()
+    }
+
+    /// Block on an arbitrary future using this client's runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Block until a long-running operation completes.
+    pub fn wait<T: Send + 'static>(&self, wait: Wait<T>) -> Result<T, Error> {
+        self.runtime.block_on(wait.result())
+    }
+
+    /// Iterate a paginated list, fetching pages on demand.
+    pub fn paginate<T: Send + 'static>(&self, paginator: Paginator<T>) -> Pages<T> {
+        Pages::new(paginator, self.runtime.clone())
+    }
+
+    fn service<S>(&self, inner: S) -> Service<S> {
+        Service::new(inner, self.runtime.clone())
+    }
+
+    // Billing services
+
+    pub fn budgets(&self) -> Service<databricks_billing::Budgets> {
+        self.service(self.inner.budgets())
+    }
+
+    pub fn usage(&self) -> Service<databricks_billing::Usage> {
+        self.service(self.inner.usage())
+    }
+
+    pub fn budget_policy(&self) -> Service<databricks_billing::BudgetPolicyService> {
+        self.service(self.inner.budget_policy())
+    }
+
+    pub fn log_delivery(&self) -> Service<databricks_billing::LogDelivery> {
+        self.service(self.inner.log_delivery())
+    }
+
+    pub fn usage_dashboards(&self) -> Service<databricks_billing::UsageDashboards> {
+        self.service(self.inner.usage_dashboards())
+    }
+
+    // Provisioning services
+
+    pub fn workspaces(&self) -> Service<databricks_provisioning::Workspaces> {
+        self.service(self.inner.workspaces())
+    }
+
+    pub fn credentials(&self) -> Service<databricks_provisioning::Credentials> {
+        self.service(self.inner.credentials())
+    }
+
+    pub fn encryption_keys(&self) -> Service<databricks_provisioning::EncryptionKeys> {
+        self.service(self.inner.encryption_keys())
+    }
+
+    pub fn networks(&self) -> Service<databricks_provisioning::Networks> {
+        self.service(self.inner.networks())
+    }
+
+    pub fn private_access(&self) -> Service<databricks_provisioning::PrivateAccess> {
+        self.service(self.inner.private_access())
+    }
+
+    pub fn storage(&self) -> Service<databricks_provisioning::Storage> {
+        self.service(self.inner.storage())
+    }
+
+    pub fn vpc_endpoints(&self) -> Service<databricks_provisioning::VpcEndpoints> {
+        self.service(self.inner.vpc_endpoints())
+    }
+
+    // Settings services (account-level)
+
+    pub fn network_connectivity(&self) -> Service<databricks_settings::NetworkConnectivity> {
+        self.service(self.inner.network_connectivity())
+    }
+
+    pub fn account_ip_access_lists(&self) -> Service<databricks_settings::AccountIpAccessLists> {
+        self.service(self.inner.account_ip_access_lists())
+    }
+
+    // IAM services (account-level)
+
+    pub fn workspace_assignment(&self) -> Service<databricks_iam::WorkspaceAssignment> {
+        self.service(self.inner.workspace_assignment())
+    }
+
+    pub fn account_access_control(&self) -> Service<databricks_iam::AccountAccessControl> {
+        self.service(self.inner.account_access_control())
+    }
+}
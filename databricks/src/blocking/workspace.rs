@@ -0,0 +1,412 @@
+use super::{runtime, Pages, Service};
+use databricks_core::config;
+use databricks_core::error::Error;
+use databricks_core::paginator::Paginator;
+use databricks_core::wait::Wait;
+use databricks_core::Client as CoreClient;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Blocking counterpart of [`crate::workspace::Client`].
+///
+/// Every accessor returns a [`Service`] bound to this client's runtime.
+pub struct Client {
+    inner: crate::workspace::Client,
+    runtime: Arc<Runtime>,
+}
+
+impl Client {
+    /// Create a workspace client from default configuration
+    /// (environment variables, `~/.databrickscfg`, etc.).
+    pub fn new() -> Result<Self, Error> {
+        let config = config::Builder::default().build()?;
+        Self::with_config(config)
+    }
+
+    /// Create a workspace client from an explicit configuration.
+    pub fn with_config(config: config::Config) -> Result<Self, Error> {
+        let inner = crate::workspace::Client::with_config(config)?;
+        Ok(Self {
+            inner,
+            runtime: runtime()?,
+        })
+    }
+
+    /// Wrap an existing core client, e.g. one built with a custom transport.
+    pub fn from_core_client(client: CoreClient) -> Result<Self, Error> {
+        Ok(Self {
+            inner: crate::workspace::Client::from_core_client(client),
+            runtime: runtime()?,
+        })
+    }
+
+    /// Access the underlying core client (for advanced/custom usage).
+    pub fn core_client(&self) -> &CoreClient {
+        self.inner.core_client()
+    }
+
+    /// Block on an arbitrary future using this client's runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Block until a long-running operation completes.
+    pub fn wait<T: Send + 'static>(&self, wait: Wait<T>) -> Result<T, Error> {
+        self.runtime.block_on(wait.result())
+    }
+
+    /// Iterate a paginated list, fetching pages on demand.
+    pub fn paginate<T: Send + 'static>(&self, paginator: Paginator<T>) -> Pages<T> {
+        Pages::new(paginator, self.runtime.clone())
+    }
+
+    fn service<S>(&self, inner: S) -> Service<S> {
+        Service::new(inner, self.runtime.clone())
+    }
+
+    // SQL services
+
+    pub fn statements(&self) -> Service<databricks_sql::Statements> {
+        self.service(self.inner.statements())
+    }
+
+    pub fn warehouses(&self) -> Service<databricks_sql::Warehouses> {
+        self.service(self.inner.warehouses())
+    }
+
+    pub fn alerts(&self) -> Service<databricks_sql::Alerts> {
+        self.service(self.inner.alerts())
+    }
+
+    pub fn queries(&self) -> Service<databricks_sql::Queries> {
+        self.service(self.inner.queries())
+    }
+
+    pub fn query_history(&self) -> Service<databricks_sql::QueryHistory> {
+        self.service(self.inner.query_history())
+    }
+
+    pub fn query_visualizations(&self) -> Service<databricks_sql::QueryVisualizations> {
+        self.service(self.inner.query_visualizations())
+    }
+
+    // Genie services
+
+    pub fn genie_spaces(&self) -> Service<databricks_genie::Spaces> {
+        self.service(self.inner.genie_spaces())
+    }
+
+    pub fn genie_conversations(
+        &self,
+        space_id: impl Into<String>,
+    ) -> Service<databricks_genie::Conversations> {
+        self.service(self.inner.genie_conversations(space_id))
+    }
+
+    // Compute services
+
+    pub fn clusters(&self) -> Service<databricks_compute::Clusters> {
+        self.service(self.inner.clusters())
+    }
+
+    pub fn instance_pools(&self) -> Service<databricks_compute::InstancePools> {
+        self.service(self.inner.instance_pools())
+    }
+
+    pub fn cluster_policies(&self) -> Service<databricks_compute::ClusterPolicies> {
+        self.service(self.inner.cluster_policies())
+    }
+
+    pub fn libraries(&self) -> Service<databricks_compute::Libraries> {
+        self.service(self.inner.libraries())
+    }
+
+    pub fn command_execution(&self) -> Service<databricks_compute::CommandExecution> {
+        self.service(self.inner.command_execution())
+    }
+
+    pub fn global_init_scripts(&self) -> Service<databricks_compute::GlobalInitScripts> {
+        self.service(self.inner.global_init_scripts())
+    }
+
+    pub fn instance_profiles(&self) -> Service<databricks_compute::InstanceProfiles> {
+        self.service(self.inner.instance_profiles())
+    }
+
+    pub fn policy_families(&self) -> Service<databricks_compute::PolicyFamilies> {
+        self.service(self.inner.policy_families())
+    }
+
+    // Jobs services
+
+    pub fn jobs(&self) -> Service<databricks_jobs::Jobs> {
+        self.service(self.inner.jobs())
+    }
+
+    pub fn runs(&self) -> Service<databricks_jobs::Runs> {
+        self.service(self.inner.runs())
+    }
+
+    // Files services
+
+    pub fn dbfs(&self) -> Service<databricks_files::Dbfs> {
+        self.service(self.inner.dbfs())
+    }
+
+    pub fn files(&self) -> Service<databricks_files::Files> {
+        self.service(self.inner.files())
+    }
+
+    // Workspace services
+
+    pub fn notebooks(&self) -> Service<databricks_workspace_api::Notebooks> {
+        self.service(self.inner.notebooks())
+    }
+
+    pub fn repos(&self) -> Service<databricks_workspace_api::Repos> {
+        self.service(self.inner.repos())
+    }
+
+    pub fn secrets(&self) -> Service<databricks_workspace_api::Secrets> {
+        self.service(self.inner.secrets())
+    }
+
+    pub fn git_credentials(&self) -> Service<databricks_workspace_api::GitCredentials> {
+        self.service(self.inner.git_credentials())
+    }
+
+    // IAM services
+
+    pub fn users(&self) -> Service<databricks_iam::Users> {
+        self.service(self.inner.users())
+    }
+
+    pub fn groups(&self) -> Service<databricks_iam::Groups> {
+        self.service(self.inner.groups())
+    }
+
+    pub fn service_principals(&self) -> Service<databricks_iam::ServicePrincipals> {
+        self.service(self.inner.service_principals())
+    }
+
+    pub fn permissions(&self) -> Service<databricks_iam::Permissions> {
+        self.service(self.inner.permissions())
+    }
+
+    pub fn object_permissions(&self) -> Service<databricks_iam::ObjectPermissions> {
+        self.service(self.inner.object_permissions())
+    }
+
+    pub fn current_user(&self) -> Service<databricks_iam::Me> {
+        self.service(self.inner.current_user())
+    }
+
+    pub fn permission_migration(&self) -> Service<databricks_iam::PermissionMigration> {
+        self.service(self.inner.permission_migration())
+    }
+
+    // Catalog services
+
+    pub fn catalogs(&self) -> Service<databricks_catalog::Catalogs> {
+        self.service(self.inner.catalogs())
+    }
+
+    pub fn schemas(&self) -> Service<databricks_catalog::Schemas> {
+        self.service(self.inner.schemas())
+    }
+
+    pub fn tables(&self) -> Service<databricks_catalog::Tables> {
+        self.service(self.inner.tables())
+    }
+
+    pub fn volumes(&self) -> Service<databricks_catalog::Volumes> {
+        self.service(self.inner.volumes())
+    }
+
+    pub fn grants(&self) -> Service<databricks_catalog::Grants> {
+        self.service(self.inner.grants())
+    }
+
+    pub fn metastores(&self) -> Service<databricks_catalog::Metastores> {
+        self.service(self.inner.metastores())
+    }
+
+    pub fn storage_credentials(&self) -> Service<databricks_catalog::StorageCredentials> {
+        self.service(self.inner.storage_credentials())
+    }
+
+    pub fn external_locations(&self) -> Service<databricks_catalog::ExternalLocations> {
+        self.service(self.inner.external_locations())
+    }
+
+    pub fn connections(&self) -> Service<databricks_catalog::Connections> {
+        self.service(self.inner.connections())
+    }
+
+    pub fn functions(&self) -> Service<databricks_catalog::Functions> {
+        self.service(self.inner.functions())
+    }
+
+    pub fn online_tables(&self) -> Service<databricks_catalog::OnlineTables> {
+        self.service(self.inner.online_tables())
+    }
+
+    pub fn table_constraints(&self) -> Service<databricks_catalog::TableConstraints> {
+        self.service(self.inner.table_constraints())
+    }
+
+    pub fn system_schemas(&self) -> Service<databricks_catalog::SystemSchemas> {
+        self.service(self.inner.system_schemas())
+    }
+
+    pub fn workspace_bindings(&self) -> Service<databricks_catalog::WorkspaceBindings> {
+        self.service(self.inner.workspace_bindings())
+    }
+
+    pub fn artifact_allowlists(&self) -> Service<databricks_catalog::ArtifactAllowlists> {
+        self.service(self.inner.artifact_allowlists())
+    }
+
+    pub fn catalog_registered_models(
+        &self,
+    ) -> Service<databricks_catalog::CatalogRegisteredModels> {
+        self.service(self.inner.catalog_registered_models())
+    }
+
+    pub fn catalog_model_versions(&self) -> Service<databricks_catalog::CatalogModelVersions> {
+        self.service(self.inner.catalog_model_versions())
+    }
+
+    // Serving services
+
+    pub fn serving_endpoints(&self) -> Service<databricks_serving::ServingEndpoints> {
+        self.service(self.inner.serving_endpoints())
+    }
+
+    // Pipelines services
+
+    pub fn pipelines(&self) -> Service<databricks_pipelines::Pipelines> {
+        self.service(self.inner.pipelines())
+    }
+
+    // ML services
+
+    pub fn experiments(&self) -> Service<databricks_ml::Experiments> {
+        self.service(self.inner.experiments())
+    }
+
+    pub fn ml_runs(&self) -> Service<databricks_ml::Runs> {
+        self.service(self.inner.ml_runs())
+    }
+
+    pub fn registered_models(&self) -> Service<databricks_ml::RegisteredModels> {
+        self.service(self.inner.registered_models())
+    }
+
+    pub fn model_versions(&self) -> Service<databricks_ml::ModelVersions> {
+        self.service(self.inner.model_versions())
+    }
+
+    pub fn model_registry(&self) -> Service<databricks_ml::ModelRegistry> {
+        self.service(self.inner.model_registry())
+    }
+
+    // Sharing services
+
+    pub fn shares(&self) -> Service<databricks_sharing::Shares> {
+        self.service(self.inner.shares())
+    }
+
+    pub fn providers(&self) -> Service<databricks_sharing::Providers> {
+        self.service(self.inner.providers())
+    }
+
+    pub fn recipients(&self) -> Service<databricks_sharing::Recipients> {
+        self.service(self.inner.recipients())
+    }
+
+    pub fn recipient_activation(&self) -> Service<databricks_sharing::RecipientActivation> {
+        self.service(self.inner.recipient_activation())
+    }
+
+    // Vector Search services
+
+    pub fn vector_search_endpoints(&self) -> Service<databricks_vectorsearch::Endpoints> {
+        self.service(self.inner.vector_search_endpoints())
+    }
+
+    pub fn vector_search_indexes(&self) -> Service<databricks_vectorsearch::Indexes> {
+        self.service(self.inner.vector_search_indexes())
+    }
+
+    // Apps services
+
+    pub fn apps(&self) -> Service<databricks_apps::Apps> {
+        self.service(self.inner.apps())
+    }
+
+    pub fn apps_settings(&self) -> Service<databricks_apps::AppsSettings> {
+        self.service(self.inner.apps_settings())
+    }
+
+    // Settings services
+
+    pub fn ip_access_lists(&self) -> Service<databricks_settings::IpAccessLists> {
+        self.service(self.inner.ip_access_lists())
+    }
+
+    pub fn tokens(&self) -> Service<databricks_settings::Tokens> {
+        self.service(self.inner.tokens())
+    }
+
+    pub fn workspace_conf(&self) -> Service<databricks_settings::WorkspaceConf> {
+        self.service(self.inner.workspace_conf())
+    }
+
+    pub fn token_management(&self) -> Service<databricks_settings::TokenManagement> {
+        self.service(self.inner.token_management())
+    }
+
+    pub fn notification_destinations(
+        &self,
+    ) -> Service<databricks_settings::NotificationDestinations> {
+        self.service(self.inner.notification_destinations())
+    }
+
+    pub fn default_namespace(&self) -> Service<databricks_settings::DefaultNamespace> {
+        self.service(self.inner.default_namespace())
+    }
+
+    pub fn restrict_workspace_admins(
+        &self,
+    ) -> Service<databricks_settings::RestrictWorkspaceAdmins> {
+        self.service(self.inner.restrict_workspace_admins())
+    }
+
+    pub fn automatic_cluster_update(&self) -> Service<databricks_settings::AutomaticClusterUpdate> {
+        self.service(self.inner.automatic_cluster_update())
+    }
+
+    pub fn compliance_security_profile(
+        &self,
+    ) -> Service<databricks_settings::ComplianceSecurityProfile> {
+        self.service(self.inner.compliance_security_profile())
+    }
+
+    pub fn enhanced_security_monitoring(
+        &self,
+    ) -> Service<databricks_settings::EnhancedSecurityMonitoring> {
+        self.service(self.inner.enhanced_security_monitoring())
+    }
+
+    pub fn personal_compute(&self) -> Service<databricks_settings::PersonalCompute> {
+        self.service(self.inner.personal_compute())
+    }
+
+    // Dashboard services
+
+    pub fn dashboards(&self) -> Service<databricks_dashboards::Dashboards> {
+        self.service(self.inner.dashboards())
+    }
+}
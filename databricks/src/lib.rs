@@ -1,4 +1,7 @@
 pub mod account;
+pub mod audit;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod workspace;
 
 pub use databricks_core::*;
@@ -23,6 +23,11 @@ impl Client {
         Ok(Self { inner })
     }
 
+    /// Wrap an existing core client, e.g. one built with a custom transport.
+    pub fn from_core_client(client: CoreClient) -> Self {
+        Self { inner: client }
+    }
+
     /// Access the underlying core client (for advanced/custom usage).
     pub fn core_client(&self) -> &CoreClient {
         &self.inner
@@ -157,6 +162,10 @@ impl Client {
         databricks_iam::Permissions::new(self.inner.clone())
     }
 
+    pub fn object_permissions(&self) -> databricks_iam::ObjectPermissions {
+        databricks_iam::ObjectPermissions::new(self.inner.clone())
+    }
+
     pub fn current_user(&self) -> databricks_iam::Me {
         databricks_iam::Me::new(self.inner.clone())
     }
@@ -1,4 +1,5 @@
-use databricks::{genie, sql, Client};
+use databricks::{genie, Client};
+use futures::TryStreamExt;
 use std::collections::HashMap;
 use std::env;
 use std::fs;
@@ -31,9 +32,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let token = config
         .get("DATABRICKS_API_KEY")
         .expect("DATABRICKS_API_KEY");
-    let warehouse_id = config
-        .get("DATABRICKS_WAREHOUSE_ID")
-        .expect("DATABRICKS_WAREHOUSE_ID");
 
     let client = Client::builder().host(host).token(token).build()?;
 
@@ -97,31 +95,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             if let Some(query) = &query_attachment.query {
                 println!("\nGenerated SQL:\n{}", query);
 
-                // Execute the query
-                println!("\nExecuting...\n");
-                let statements = sql::Statements::new(client.clone());
-                let request = sql::Request::new(query, warehouse_id);
-                let response = statements
-                    .execute_wait(&request, Duration::from_secs(1), Duration::from_secs(60))
+                // Fetch Genie's result for the query, across all chunks
+                let Some(attachment_id) = &attachment.attachment_id else {
+                    continue;
+                };
+                let mut rows = conversations
+                    .attachment_result_stream(
+                        &message.conversation_id,
+                        &message.message_id,
+                        attachment_id,
+                    )
                     .await?;
 
-                // Print column headers
-                if let Some(manifest) = &response.manifest {
-                    if let Some(schema) = &manifest.schema {
-                        let headers: Vec<_> =
-                            schema.columns.iter().map(|c| c.name.as_str()).collect();
-                        println!("{}", headers.join(" | "));
-                        println!("{}", "-".repeat(headers.join(" | ").len()));
-                    }
-                }
+                let headers: Vec<_> = rows.columns().iter().map(|c| c.name.clone()).collect();
+                println!("\n{}", headers.join(" | "));
+                println!("{}", "-".repeat(headers.join(" | ").len()));
 
-                // Print rows
-                if let Some(result) = &response.result {
-                    for row in &result.data_array {
-                        let values: Vec<_> =
-                            row.iter().map(|v| v.as_deref().unwrap_or("NULL")).collect();
-                        println!("{}", values.join(" | "));
-                    }
+                while let Some(row) = rows.try_next().await? {
+                    let values: Vec<_> =
+                        row.iter().map(|v| v.as_deref().unwrap_or("NULL")).collect();
+                    println!("{}", values.join(" | "));
                 }
             }
         }
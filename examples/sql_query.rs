@@ -38,24 +38,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Check warehouse state
     let warehouses = sql::Warehouses::new(client.clone());
-    let wh = warehouses.get(warehouse_id).await?;
+    let wh = warehouses
+        .ensure_running(
+            warehouse_id,
+            Duration::from_secs(2),
+            Duration::from_secs(600),
+        )
+        .await?;
     println!("Warehouse: {} ({:?})", wh.name, wh.state);
 
-    if !wh.state.is_running() {
-        println!("Starting warehouse...");
-        warehouses.start(warehouse_id).await?;
-
-        // Poll until running
-        loop {
-            tokio::time::sleep(Duration::from_secs(2)).await;
-            let wh = warehouses.get(warehouse_id).await?;
-            println!("  State: {:?}", wh.state);
-            if wh.state.is_running() {
-                break;
-            }
-        }
-    }
-
     let statements = sql::Statements::new(client.clone());
     let catalog = sql::Catalog::new(statements, warehouse_id);
 
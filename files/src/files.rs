@@ -13,14 +13,11 @@ impl Files {
         Self { client }
     }
 
-    /// Upload a file to Unity Catalog volumes path.
+    /// Upload a file to Unity Catalog volumes path, replacing any existing file.
     pub async fn upload(&self, path: &str, data: &[u8]) -> Result<(), Error> {
         // Files API uses PUT with raw bytes at the path
-        let uri = format!("{}/{}", PATH, path.trim_start_matches('/'));
-        let _ = (uri, data);
-        Err(Error::Other(
-            "Binary upload not yet implemented for Files API. Use Dbfs instead.".into(),
-        ))
+        let uri = format!("{}/{}?overwrite=true", PATH, path.trim_start_matches('/'));
+        self.client.put_bytes(&uri, data).await
     }
 
     /// Download a file from Unity Catalog volumes path.
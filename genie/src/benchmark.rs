@@ -0,0 +1,540 @@
+use crate::conversations::Conversations;
+use crate::types::Message;
+use databricks_core::runtime::Instant;
+use databricks_core::{Client, Error};
+use databricks_sql::{Request, Statements};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// A business question and the answer Genie should produce.
+///
+/// The expected answer is either literal `rows` or an `sql` statement whose
+/// result is the answer. Cells in `rows` may be strings, numbers, booleans
+/// or null.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkQuestion {
+    /// Identifies the question in the report; defaults to its position.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub question: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sql: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rows: Option<Vec<Vec<Value>>>,
+}
+
+impl BenchmarkQuestion {
+    pub fn with_sql(question: impl Into<String>, sql: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            question: question.into(),
+            sql: Some(sql.into()),
+            rows: None,
+        }
+    }
+
+    pub fn with_rows(question: impl Into<String>, rows: Vec<Vec<Value>>) -> Self {
+        Self {
+            id: None,
+            question: question.into(),
+            sql: None,
+            rows: Some(rows),
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+}
+
+/// A list of [`BenchmarkQuestion`]s, stored as JSON.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuestionSet {
+    pub questions: Vec<BenchmarkQuestion>,
+}
+
+impl QuestionSet {
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| Error::Other(format!("failed to read {}: {}", path.display(), e)))?;
+        Self::from_json(&json)
+    }
+}
+
+/// How far apart two numeric cells may be and still match.
+///
+/// Cells match if `|a - b| <= absolute + relative * |expected|`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            absolute: 1e-6,
+            relative: 1e-6,
+        }
+    }
+}
+
+/// Outcome of one benchmark question.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "verdict", content = "reason", rename_all = "snake_case")]
+pub enum Verdict {
+    Pass,
+    /// Genie's result differs from the expected answer.
+    Mismatch(String),
+    /// Genie answered without generating SQL.
+    NoQuery,
+    /// Genie, the generated SQL or the expected SQL failed.
+    Error(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QuestionResult {
+    pub id: String,
+    pub question: String,
+    pub verdict: Verdict,
+    pub conversation_id: Option<String>,
+    pub generated_sql: Option<String>,
+}
+
+/// Results of a [`Benchmark`] run.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchmarkReport {
+    pub space_id: String,
+    pub results: Vec<QuestionResult>,
+    #[serde(skip)]
+    pub elapsed: Duration,
+}
+
+impl BenchmarkReport {
+    pub fn passed(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| r.verdict == Verdict::Pass)
+            .count()
+    }
+
+    /// Fraction of questions answered correctly, from 0.0 to 1.0.
+    pub fn accuracy(&self) -> f64 {
+        if self.results.is_empty() {
+            return 0.0;
+        }
+        self.passed() as f64 / self.results.len() as f64
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &QuestionResult> {
+        self.results.iter().filter(|r| r.verdict != Verdict::Pass)
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# Genie benchmark: `{}`\n\nAccuracy: {}/{} ({:.1}%)\n",
+            self.space_id,
+            self.passed(),
+            self.results.len(),
+            self.accuracy() * 100.0
+        );
+        let _ = writeln!(out, "| ID | Question | Verdict |\n|---|---|---|");
+        for result in &self.results {
+            let verdict = match &result.verdict {
+                Verdict::Pass => "pass".to_string(),
+                Verdict::Mismatch(reason) => format!("mismatch: {}", reason),
+                Verdict::NoQuery => "no query".to_string(),
+                Verdict::Error(error) => format!("error: {}", error),
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} |",
+                result.id,
+                result.question.replace('|', "\\|"),
+                verdict.replace('|', "\\|")
+            );
+        }
+        out
+    }
+}
+
+/// Measures how accurately a Genie space answers a [`QuestionSet`].
+///
+/// Each question starts a new conversation. The SQL Genie generates and any
+/// expected SQL run on `warehouse_id`, and the results are compared as sets
+/// of rows: order and duplicates are ignored, columns are compared by
+/// position, and numbers match within a [`Tolerance`].
+///
+/// ```no_run
+/// # async fn run(client: databricks_core::Client) -> Result<(), databricks_core::Error> {
+/// use databricks_genie::{Benchmark, QuestionSet};
+///
+/// let questions = QuestionSet::read("benchmarks/sales.json")?;
+/// let report = Benchmark::new(client, "space-id", "warehouse-id")
+///     .run(&questions)
+///     .await;
+/// println!("{}", report.to_markdown());
+/// assert!(report.accuracy() >= 0.9);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Benchmark {
+    client: Client,
+    conversations: Conversations,
+    warehouse_id: String,
+    poll_interval: Duration,
+    timeout: Duration,
+    tolerance: Tolerance,
+}
+
+impl Benchmark {
+    pub fn new(
+        client: Client,
+        space_id: impl Into<String>,
+        warehouse_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            conversations: Conversations::new(client.clone(), space_id),
+            client,
+            warehouse_id: warehouse_id.into(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: DEFAULT_TIMEOUT,
+            tolerance: Tolerance::default(),
+        }
+    }
+
+    /// Interval between Genie and statement status polls (default 1s).
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Maximum time for each Genie response and each statement (default 5
+    /// minutes).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Ask every question in turn. Failures are recorded in the report
+    /// rather than ending the run.
+    pub async fn run(&self, questions: &QuestionSet) -> BenchmarkReport {
+        let start = Instant::now();
+        let mut results = Vec::with_capacity(questions.questions.len());
+        for (i, question) in questions.questions.iter().enumerate() {
+            results.push(self.evaluate(i, question).await);
+        }
+        BenchmarkReport {
+            space_id: self.conversations.space_id().to_string(),
+            results,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    async fn evaluate(&self, index: usize, question: &BenchmarkQuestion) -> QuestionResult {
+        let mut result = QuestionResult {
+            id: question
+                .id
+                .clone()
+                .unwrap_or_else(|| (index + 1).to_string()),
+            question: question.question.clone(),
+            verdict: Verdict::NoQuery,
+            conversation_id: None,
+            generated_sql: None,
+        };
+
+        let message = match self
+            .conversations
+            .start_wait(&question.question, self.poll_interval, self.timeout)
+            .await
+        {
+            Ok(message) => message,
+            Err(e) => {
+                result.verdict = Verdict::Error(format!("Genie: {}", e));
+                return result;
+            }
+        };
+        result.conversation_id = Some(message.conversation_id.clone());
+        let Some(sql) = generated_sql(&message) else {
+            return result;
+        };
+        result.generated_sql = Some(sql.clone());
+
+        let actual = match self.query(&sql).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                result.verdict = Verdict::Error(format!("generated SQL: {}", e));
+                return result;
+            }
+        };
+        let expected = match (&question.rows, &question.sql) {
+            (Some(rows), _) => rows
+                .iter()
+                .map(|row| row.iter().map(cell).collect())
+                .collect(),
+            (None, Some(sql)) => match self.query(sql).await {
+                Ok(rows) => rows,
+                Err(e) => {
+                    result.verdict = Verdict::Error(format!("expected SQL: {}", e));
+                    return result;
+                }
+            },
+            (None, None) => {
+                result.verdict = Verdict::Error("question has no expected rows or SQL".into());
+                return result;
+            }
+        };
+
+        result.verdict = match compare(&expected, &actual, self.tolerance) {
+            Ok(()) => Verdict::Pass,
+            Err(reason) => Verdict::Mismatch(reason),
+        };
+        result
+    }
+
+    async fn query(&self, sql: &str) -> Result<Vec<Vec<Option<String>>>, Error> {
+        let statements = Statements::new(self.client.clone());
+        let response = statements
+            .execute_wait(
+                &Request::new(sql, &self.warehouse_id),
+                self.poll_interval,
+                self.timeout,
+            )
+            .await?;
+        statements.result_stream(&response).collect_all().await
+    }
+}
+
+fn generated_sql(message: &Message) -> Option<String> {
+    message
+        .attachments
+        .iter()
+        .filter_map(|a| a.query.as_ref()?.query.clone())
+        .find(|sql| !sql.trim().is_empty())
+}
+
+fn cell(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Compare two results as sets of rows.
+fn compare(
+    expected: &[Vec<Option<String>>],
+    actual: &[Vec<Option<String>>],
+    tolerance: Tolerance,
+) -> Result<(), String> {
+    let width = |rows: &[Vec<Option<String>>]| rows.first().map(Vec::len);
+    if let (Some(e), Some(a)) = (width(expected), width(actual)) {
+        if e != a {
+            return Err(format!("expected {} columns, got {}", e, a));
+        }
+    }
+
+    let matches = |a: &[Option<String>], b: &[Option<String>]| {
+        a.len() == b.len()
+            && a.iter()
+                .zip(b)
+                .all(|(a, b)| cells_match(a.as_deref(), b.as_deref(), tolerance))
+    };
+    if let Some(missing) = expected
+        .iter()
+        .find(|e| !actual.iter().any(|a| matches(e, a)))
+    {
+        return Err(format!("missing row {}", format_row(missing)));
+    }
+    if let Some(extra) = actual
+        .iter()
+        .find(|a| !expected.iter().any(|e| matches(e, a)))
+    {
+        return Err(format!("unexpected row {}", format_row(extra)));
+    }
+    Ok(())
+}
+
+fn cells_match(expected: Option<&str>, actual: Option<&str>, tolerance: Tolerance) -> bool {
+    match (expected, actual) {
+        (None, None) => true,
+        (Some(e), Some(a)) if e == a => true,
+        (Some(e), Some(a)) => match (e.trim().parse::<f64>(), a.trim().parse::<f64>()) {
+            (Ok(e), Ok(a)) => (e - a).abs() <= tolerance.absolute + tolerance.relative * e.abs(),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn format_row(row: &[Option<String>]) -> String {
+    let cells: Vec<&str> = row.iter().map(|c| c.as_deref().unwrap_or("NULL")).collect();
+    format!("({})", cells.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use databricks_core::transport::mock::MockTransport;
+    use serde_json::json;
+
+    fn statement(rows: Value) -> Value {
+        json!({
+            "statement_id": "st",
+            "status": {"state": "SUCCEEDED"},
+            "result": {"data_array": rows},
+        })
+    }
+
+    fn started(conversation_id: &str) -> Value {
+        json!({
+            "conversation": {"id": conversation_id, "space_id": "s1"},
+            "message": {"message_id": "m1", "space_id": "s1", "conversation_id": conversation_id},
+            "message_id": "m1",
+        })
+    }
+
+    fn completed(conversation_id: &str, sql: &str) -> Value {
+        json!({
+            "message_id": "m1",
+            "space_id": "s1",
+            "conversation_id": conversation_id,
+            "status": "COMPLETED",
+            "attachments": [{"attachment_id": "a1", "query": {"query": sql}}],
+        })
+    }
+
+    #[tokio::test]
+    async fn test_benchmark_with_mock_transport() {
+        let messages = "/api/2.0/genie/spaces/s1/conversations";
+        let mock = MockTransport::new()
+            .on(
+                "POST",
+                "/api/2.0/genie/spaces/s1/start-conversation",
+                200,
+                started("c1"),
+            )
+            .on(
+                "POST",
+                "/api/2.0/genie/spaces/s1/start-conversation",
+                200,
+                started("c2"),
+            )
+            .on(
+                "POST",
+                "/api/2.0/genie/spaces/s1/start-conversation",
+                200,
+                started("c3"),
+            )
+            .on(
+                "GET",
+                &format!("{}/c1/messages/m1", messages),
+                200,
+                completed("c1", "SELECT region, revenue"),
+            )
+            .on(
+                "GET",
+                &format!("{}/c2/messages/m1", messages),
+                200,
+                completed("c2", "SELECT count(*)"),
+            )
+            .on(
+                "GET",
+                &format!("{}/c3/messages/m1", messages),
+                200,
+                json!({
+                    "message_id": "m1", "space_id": "s1", "conversation_id": "c3",
+                    "status": "COMPLETED",
+                    "attachments": [{"text": {"content": "Which year?"}}],
+                }),
+            )
+            // Generated SQL of question 1, then generated and expected SQL of
+            // question 2.
+            .on(
+                "POST",
+                "/api/2.0/sql/statements",
+                200,
+                statement(json!([["west", "10.0000001"], ["east", "5"]])),
+            )
+            .on(
+                "POST",
+                "/api/2.0/sql/statements",
+                200,
+                statement(json!([["41"]])),
+            )
+            .on(
+                "POST",
+                "/api/2.0/sql/statements",
+                200,
+                statement(json!([["42"]])),
+            );
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let questions = QuestionSet::from_json(
+            &json!({"questions": [
+                {"id": "revenue", "question": "Revenue by region?", "rows": [["east", 5], ["west", 10]]},
+                {"question": "How many orders?", "sql": "SELECT 42"},
+                {"question": "Revenue last year?", "rows": [[1]]},
+            ]})
+            .to_string(),
+        )
+        .unwrap();
+        let report = Benchmark::new(client, "s1", "wh")
+            .poll_interval(Duration::ZERO)
+            .run(&questions)
+            .await;
+
+        let verdicts: Vec<_> = report
+            .results
+            .iter()
+            .map(|r| (r.id.as_str(), &r.verdict))
+            .collect();
+        assert_eq!(
+            verdicts,
+            [
+                ("revenue", &Verdict::Pass),
+                ("2", &Verdict::Mismatch("missing row (42)".into())),
+                ("3", &Verdict::NoQuery),
+            ]
+        );
+        assert_eq!(report.passed(), 1);
+        assert_eq!(
+            report.results[1].generated_sql.as_deref(),
+            Some("SELECT count(*)")
+        );
+        let statements: Vec<_> = mock
+            .requests()
+            .into_iter()
+            .filter(|r| r.path == "/api/2.0/sql/statements")
+            .filter_map(|r| r.body?["statement"].as_str().map(str::to_string))
+            .collect();
+        assert_eq!(
+            statements,
+            ["SELECT region, revenue", "SELECT count(*)", "SELECT 42"]
+        );
+    }
+}
@@ -1,10 +1,13 @@
 use crate::types::{
-    CreateMessageRequest, ExecuteQueryResponse, ListConversationsResponse, ListMessagesResponse,
-    Message, SendMessageFeedbackRequest, StartConversationRequest, StartConversationResponse,
+    ConversationSummary, CreateMessageRequest, ExecuteQueryResponse, ListConversationsResponse,
+    ListMessagesResponse, Message, SendMessageFeedbackRequest, StartConversationRequest,
+    StartConversationResponse, StatementResponse,
 };
+use databricks_core::paginator::{Page, Paginator};
+use databricks_core::runtime::Instant;
 use databricks_core::{Client, Error};
+use databricks_sql::ResultStream;
 use std::time::Duration;
-use tokio::time::sleep;
 
 pub struct Conversations {
     client: Client,
@@ -102,14 +105,14 @@ impl Conversations {
         poll_interval: Duration,
         timeout: Duration,
     ) -> Result<Message, Error> {
-        let start = std::time::Instant::now();
+        let start = Instant::now();
 
         loop {
             if start.elapsed() > timeout {
                 return Err(Error::Timeout("Genie response timed out".into()));
             }
 
-            sleep(poll_interval).await;
+            self.client.runtime().sleep(poll_interval).await;
 
             let message = self.get_message(conversation_id, message_id).await?;
 
@@ -143,14 +146,34 @@ impl Conversations {
         self.client.post_empty(&path).await
     }
 
-    /// List all messages in a conversation
+    /// List all messages in a conversation, following every page.
     pub async fn list_messages(&self, conversation_id: &str) -> Result<Vec<Message>, Error> {
+        let mut messages = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let response = self
+                .list_messages_page(conversation_id, page_token.as_deref())
+                .await?;
+            messages.extend(response.messages);
+            match response.next_page_token.filter(|t| !t.is_empty()) {
+                Some(token) => page_token = Some(token),
+                None => return Ok(messages),
+            }
+        }
+    }
+
+    /// List one page of messages in a conversation.
+    pub async fn list_messages_page(
+        &self,
+        conversation_id: &str,
+        page_token: Option<&str>,
+    ) -> Result<ListMessagesResponse, Error> {
         let path = format!(
             "/api/2.0/genie/spaces/{}/conversations/{}/messages",
             self.space_id, conversation_id
         );
-        let response: ListMessagesResponse = self.client.get(&path).await?;
-        Ok(response.messages)
+        let query: Vec<(&str, &str)> = page_token.map(|t| ("page_token", t)).into_iter().collect();
+        self.client.get_with_query(&path, &query).await
     }
 
     /// Delete a conversation.
@@ -175,10 +198,48 @@ impl Conversations {
         self.client.delete_empty(&path).await
     }
 
-    /// List conversations in the space.
+    /// List the caller's conversations in the space.
     pub async fn list_conversations(&self) -> Result<ListConversationsResponse, Error> {
+        self.list_conversations_page(None, false).await
+    }
+
+    /// List one page of conversations in the space.
+    ///
+    /// Only the caller's conversations are listed unless `include_all` is
+    /// set, which requires CAN MANAGE on the space.
+    pub async fn list_conversations_page(
+        &self,
+        page_token: Option<&str>,
+        include_all: bool,
+    ) -> Result<ListConversationsResponse, Error> {
         let path = format!("/api/2.0/genie/spaces/{}/conversations", self.space_id);
-        self.client.get(&path).await
+        let mut query: Vec<(&str, &str)> = Vec::new();
+        if let Some(token) = page_token {
+            query.push(("page_token", token));
+        }
+        if include_all {
+            query.push(("include_all", "true"));
+        }
+        self.client.get_with_query(&path, &query).await
+    }
+
+    /// Iterate conversations in the space, fetching pages on demand.
+    ///
+    /// See [`Conversations::list_conversations_page`] for `include_all`.
+    pub fn list_all_conversations(&self, include_all: bool) -> Paginator<ConversationSummary> {
+        let space_id = self.space_id.clone();
+        Paginator::new(self.client.clone(), move |client, token| {
+            let conversations = Conversations::new(client, space_id.clone());
+            async move {
+                let response = conversations
+                    .list_conversations_page(token.as_deref(), include_all)
+                    .await?;
+                Ok(Page {
+                    items: response.conversations,
+                    next_token: response.next_page_token.filter(|t| !t.is_empty()),
+                })
+            }
+        })
     }
 
     /// Send feedback for a message.
@@ -208,4 +269,121 @@ impl Conversations {
         );
         self.client.get(&path).await
     }
+
+    /// Result of a query attachment, re-running the query if Genie has no
+    /// stored result for it.
+    ///
+    /// Errors fetching the stored result are returned as-is. Fails if the
+    /// query did not succeed.
+    pub async fn attachment_result(
+        &self,
+        conversation_id: &str,
+        message_id: &str,
+        attachment_id: &str,
+    ) -> Result<StatementResponse, Error> {
+        let stored = self
+            .get_message_attachment_query_result(conversation_id, message_id, attachment_id)
+            .await?;
+        let response = match stored.statement_response {
+            Some(response) => response,
+            None => self
+                .execute_attachment_query(conversation_id, message_id, attachment_id)
+                .await?
+                .statement_response
+                .ok_or_else(|| Error::Other("Genie returned no query result".into()))?,
+        };
+
+        if !response.status.state.is_success() {
+            let message = response
+                .status
+                .error
+                .and_then(|e| e.message)
+                .unwrap_or_else(|| format!("Query {:?}", response.status.state));
+            return Err(Error::Other(message));
+        }
+        Ok(response)
+    }
+
+    /// Stream every row of a query attachment's result, across all chunks.
+    pub async fn attachment_result_stream(
+        &self,
+        conversation_id: &str,
+        message_id: &str,
+        attachment_id: &str,
+    ) -> Result<ResultStream, Error> {
+        let response = self
+            .attachment_result(conversation_id, message_id, attachment_id)
+            .await?;
+        Ok(ResultStream::new(self.client.clone(), &response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use databricks_core::error::Code;
+    use databricks_core::transport::mock::MockTransport;
+    use serde_json::json;
+
+    const ATTACHMENT: &str = "/api/2.0/genie/spaces/s1/conversations/c1/messages/m1/attachments/a1";
+
+    fn conversations(mock: &MockTransport) -> Conversations {
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        Conversations::new(client, "s1")
+    }
+
+    #[tokio::test]
+    async fn test_attachment_result_reexecutes_only_when_result_is_missing() {
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                &format!("{}/query-result", ATTACHMENT),
+                200,
+                json!({}),
+            )
+            .on(
+                "POST",
+                &format!("{}/execute-query", ATTACHMENT),
+                200,
+                json!({"statement_response": {
+                    "statement_id": "st1",
+                    "status": {"state": "SUCCEEDED"},
+                }}),
+            );
+
+        let response = conversations(&mock)
+            .attachment_result("c1", "m1", "a1")
+            .await
+            .unwrap();
+        assert_eq!(response.statement_id, "st1");
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_attachment_result_returns_fetch_errors() {
+        let mock = MockTransport::new().on(
+            "GET",
+            &format!("{}/query-result", ATTACHMENT),
+            403,
+            json!({"error_code": "PERMISSION_DENIED", "message": "no access"}),
+        );
+
+        let err = conversations(&mock)
+            .attachment_result("c1", "m1", "a1")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Api {
+                code: Code::PermissionDenied,
+                ..
+            }
+        ));
+        assert_eq!(mock.requests().len(), 1);
+    }
 }
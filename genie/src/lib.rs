@@ -1,7 +1,19 @@
+mod benchmark;
 mod conversations;
+mod session;
+mod space_definition;
 mod spaces;
+mod transcript;
 mod types;
 
+pub use benchmark::{
+    Benchmark, BenchmarkQuestion, BenchmarkReport, QuestionResult, QuestionSet, Tolerance, Verdict,
+};
 pub use conversations::Conversations;
+pub use session::{GenieEvent, Session};
+pub use space_definition::{ImportTarget, SpaceDefinition, SPACE_DEFINITION_VERSION};
 pub use spaces::Spaces;
+pub use transcript::{
+    ConversationExporter, Transcript, TranscriptFormat, TranscriptMessage, TranscriptQuery,
+};
 pub use types::*;
@@ -0,0 +1,382 @@
+use crate::conversations::Conversations;
+use crate::types::{Attachment, Message, StatementResponse, Status, TextAttachmentPurpose};
+use databricks_core::runtime::Instant;
+use databricks_core::{Client, Error};
+use futures::stream::{self, Stream};
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Progress of a Genie response, as yielded by [`Session::ask`].
+#[derive(Debug, Clone)]
+pub enum GenieEvent {
+    /// The message moved to a new status, e.g. `FetchingMetadata` or
+    /// `ExecutingQuery`.
+    Status(Status),
+    /// Text written by Genie.
+    Text {
+        attachment_id: String,
+        content: String,
+    },
+    /// Genie is asking the user to clarify their question.
+    FollowUpQuestion {
+        attachment_id: String,
+        content: String,
+    },
+    /// SQL generated to answer the question.
+    Query {
+        attachment_id: String,
+        sql: String,
+        title: Option<String>,
+        description: Option<String>,
+    },
+    /// Result of a generated query, fetched once the message completed.
+    ///
+    /// Only the first chunk is included; read the rest with
+    /// [`databricks_sql::ResultStream`].
+    QueryResult {
+        attachment_id: String,
+        response: StatementResponse,
+    },
+    SuggestedQuestions(Vec<String>),
+    /// The final message. Always the last event of a successful response.
+    Completed(Message),
+}
+
+/// A Genie conversation that yields each response as a stream of events.
+///
+/// The first [`Session::ask`] starts a conversation; later calls continue
+/// it. Results of generated queries are fetched automatically once the
+/// response completes.
+///
+/// ```no_run
+/// # async fn run(client: databricks_core::Client) -> Result<(), databricks_core::Error> {
+/// use databricks_genie::{GenieEvent, Session};
+/// use futures::{pin_mut, TryStreamExt};
+///
+/// let mut session = Session::new(client, "space-id");
+/// let events = session.ask("What were last week's top products?").await?;
+/// pin_mut!(events);
+/// while let Some(event) = events.try_next().await? {
+///     match event {
+///         GenieEvent::Status(status) => println!("{:?}...", status),
+///         GenieEvent::Text { content, .. } => println!("{}", content),
+///         GenieEvent::Query { sql, .. } => println!("{}", sql),
+///         _ => {}
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Session {
+    client: Client,
+    space_id: String,
+    conversation_id: Option<String>,
+    poll_interval: Duration,
+    timeout: Duration,
+    fetch_results: bool,
+}
+
+impl Session {
+    pub fn new(client: Client, space_id: impl Into<String>) -> Self {
+        Self {
+            client,
+            space_id: space_id.into(),
+            conversation_id: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: DEFAULT_TIMEOUT,
+            fetch_results: true,
+        }
+    }
+
+    /// Continue an existing conversation.
+    pub fn resume(mut self, conversation_id: impl Into<String>) -> Self {
+        self.conversation_id = Some(conversation_id.into());
+        self
+    }
+
+    /// Interval between message status polls (default 1s).
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Maximum time to wait for each response (default 5 minutes).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Whether to fetch query results when a response completes (default
+    /// true).
+    pub fn fetch_results(mut self, fetch: bool) -> Self {
+        self.fetch_results = fetch;
+        self
+    }
+
+    /// The conversation, once the first question has been asked.
+    pub fn conversation_id(&self) -> Option<&str> {
+        self.conversation_id.as_deref()
+    }
+
+    /// Send a question and stream Genie's response.
+    ///
+    /// The stream ends after [`GenieEvent::Completed`], or with an error if
+    /// the response failed or timed out.
+    pub async fn ask(
+        &mut self,
+        content: impl Into<String>,
+    ) -> Result<impl Stream<Item = Result<GenieEvent, Error>>, Error> {
+        let conversations = Conversations::new(self.client.clone(), &self.space_id);
+        let message = match &self.conversation_id {
+            Some(conversation_id) => conversations.send(conversation_id, content).await?,
+            None => {
+                let response = conversations.start(content).await?;
+                self.conversation_id = Some(response.conversation.id);
+                response.message
+            }
+        };
+
+        let state = Poll {
+            client: self.client.clone(),
+            conversations,
+            poll_interval: self.poll_interval,
+            timeout: self.timeout,
+            fetch_results: self.fetch_results,
+            start: Instant::now(),
+            message: Some(message),
+            tracker: Tracker::default(),
+            pending: VecDeque::new(),
+            done: false,
+        };
+        Ok(stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(event) = state.pending.pop_front() {
+                    return Some((event, state));
+                }
+                if state.done {
+                    return None;
+                }
+                state.poll().await;
+            }
+        }))
+    }
+}
+
+struct Poll {
+    client: Client,
+    conversations: Conversations,
+    poll_interval: Duration,
+    timeout: Duration,
+    fetch_results: bool,
+    start: Instant,
+    /// A message already fetched and not yet processed.
+    message: Option<Message>,
+    tracker: Tracker,
+    pending: VecDeque<Result<GenieEvent, Error>>,
+    done: bool,
+}
+
+impl Poll {
+    async fn poll(&mut self) {
+        let message = match self.message.take() {
+            Some(message) => message,
+            None => {
+                if self.start.elapsed() > self.timeout {
+                    return self.fail(Error::Timeout("Genie response timed out".into()));
+                }
+                self.client.runtime().sleep(self.poll_interval).await;
+                match self
+                    .conversations
+                    .get_message(&self.tracker.conversation_id, &self.tracker.message_id)
+                    .await
+                {
+                    Ok(message) => message,
+                    Err(e) => return self.fail(e),
+                }
+            }
+        };
+
+        self.pending
+            .extend(self.tracker.update(&message).into_iter().map(Ok));
+
+        let Some(status) = message.status.filter(Status::is_terminal) else {
+            return;
+        };
+        if !status.is_success() {
+            let error = message
+                .error
+                .and_then(|e| e.message)
+                .unwrap_or_else(|| format!("Genie failed with status: {:?}", status));
+            return self.fail(Error::Other(error));
+        }
+
+        if self.fetch_results {
+            for attachment_id in query_attachment_ids(&message.attachments) {
+                let result = self
+                    .conversations
+                    .attachment_result(
+                        &message.conversation_id,
+                        &message.message_id,
+                        &attachment_id,
+                    )
+                    .await;
+                match result {
+                    Ok(response) => self.pending.push_back(Ok(GenieEvent::QueryResult {
+                        attachment_id,
+                        response,
+                    })),
+                    Err(e) => return self.fail(e),
+                }
+            }
+        }
+        self.pending.push_back(Ok(GenieEvent::Completed(message)));
+        self.done = true;
+    }
+
+    fn fail(&mut self, error: Error) {
+        self.pending.push_back(Err(error));
+        self.done = true;
+    }
+}
+
+/// What has already been reported for a message.
+#[derive(Default)]
+struct Tracker {
+    conversation_id: String,
+    message_id: String,
+    status: Option<Status>,
+    seen: HashSet<String>,
+}
+
+impl Tracker {
+    fn update(&mut self, message: &Message) -> Vec<GenieEvent> {
+        self.conversation_id = message.conversation_id.clone();
+        self.message_id = message.message_id.clone();
+
+        let mut events = Vec::new();
+        if let Some(status) = message.status {
+            if self.status != Some(status) {
+                self.status = Some(status);
+                events.push(GenieEvent::Status(status));
+            }
+        }
+
+        for (i, attachment) in message.attachments.iter().enumerate() {
+            let attachment_id = attachment
+                .attachment_id
+                .clone()
+                .unwrap_or_else(|| i.to_string());
+
+            if let Some(query) = &attachment.query {
+                if let Some(sql) = query.query.as_ref().filter(|s| !s.is_empty()) {
+                    if self.seen.insert(format!("query:{}", attachment_id)) {
+                        events.push(GenieEvent::Query {
+                            attachment_id: attachment_id.clone(),
+                            sql: sql.clone(),
+                            title: query.title.clone(),
+                            description: query.description.clone(),
+                        });
+                    }
+                }
+            }
+
+            if let Some(text) = &attachment.text {
+                if let Some(content) = text.content.as_ref().filter(|s| !s.is_empty()) {
+                    if self.seen.insert(format!("text:{}", attachment_id)) {
+                        let attachment_id = attachment_id.clone();
+                        let content = content.clone();
+                        events.push(match text.purpose {
+                            Some(TextAttachmentPurpose::FollowUpQuestion) => {
+                                GenieEvent::FollowUpQuestion {
+                                    attachment_id,
+                                    content,
+                                }
+                            }
+                            None => GenieEvent::Text {
+                                attachment_id,
+                                content,
+                            },
+                        });
+                    }
+                }
+            }
+
+            if let Some(suggestions) = &attachment.suggested_questions {
+                if !suggestions.questions.is_empty()
+                    && self.seen.insert(format!("suggestions:{}", attachment_id))
+                {
+                    events.push(GenieEvent::SuggestedQuestions(
+                        suggestions.questions.clone(),
+                    ));
+                }
+            }
+        }
+        events
+    }
+}
+
+fn query_attachment_ids(attachments: &[Attachment]) -> Vec<String> {
+    attachments
+        .iter()
+        .filter(|a| a.query.is_some())
+        .filter_map(|a| a.attachment_id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(status: &str, attachments: serde_json::Value) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "message_id": "m1",
+            "space_id": "s1",
+            "conversation_id": "c1",
+            "status": status,
+            "attachments": attachments,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tracker_reports_each_change_once() {
+        let mut tracker = Tracker::default();
+
+        let events = tracker.update(&message("FETCHING_METADATA", serde_json::json!([])));
+        assert!(matches!(
+            events.as_slice(),
+            [GenieEvent::Status(Status::FetchingMetadata)]
+        ));
+
+        let query = serde_json::json!([
+            {"attachment_id": "a1", "query": {"query": "SELECT 1", "title": "One"}},
+        ]);
+        let events = tracker.update(&message("EXECUTING_QUERY", query.clone()));
+        assert!(matches!(
+            events.as_slice(),
+            [GenieEvent::Status(Status::ExecutingQuery), GenieEvent::Query { sql, .. }]
+                if sql == "SELECT 1"
+        ));
+        assert!(tracker
+            .update(&message("EXECUTING_QUERY", query))
+            .is_empty());
+
+        let done = serde_json::json!([
+            {"attachment_id": "a1", "query": {"query": "SELECT 1"}},
+            {"attachment_id": "a2", "text": {"content": "Which region?", "purpose": "FOLLOW_UP_QUESTION"}},
+            {"attachment_id": "a3", "suggested_questions": {"questions": ["By month?"]}},
+        ]);
+        let events = tracker.update(&message("COMPLETED", done));
+        assert!(matches!(
+            events.as_slice(),
+            [
+                GenieEvent::Status(Status::Completed),
+                GenieEvent::FollowUpQuestion { .. },
+                GenieEvent::SuggestedQuestions(_),
+            ]
+        ));
+    }
+}
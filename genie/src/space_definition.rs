@@ -0,0 +1,197 @@
+use crate::spaces::Spaces;
+use crate::types::{CreateSpaceRequest, Space, UpdateSpaceRequest};
+use databricks_core::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+/// Current version of the [`SpaceDefinition`] file format.
+pub const SPACE_DEFINITION_VERSION: u32 = 1;
+
+/// A Genie space's configuration (instructions, tables, sample questions),
+/// detached from any workspace.
+///
+/// The configuration is the space's `serialized_space`, stored parsed so
+/// the file diffs cleanly. Edit `warehouse_id` before importing into a
+/// workspace with different warehouses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpaceDefinition {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warehouse_id: Option<String>,
+    pub space: Value,
+}
+
+impl SpaceDefinition {
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let definition: Self = serde_json::from_str(json)?;
+        if definition.version > SPACE_DEFINITION_VERSION {
+            return Err(Error::Other(format!(
+                "unsupported space definition version {} (latest is {})",
+                definition.version, SPACE_DEFINITION_VERSION
+            )));
+        }
+        Ok(definition)
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_json()?)
+            .map_err(|e| Error::Other(format!("failed to write {}: {}", path.display(), e)))
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| Error::Other(format!("failed to read {}: {}", path.display(), e)))?;
+        Self::from_json(&json)
+    }
+
+    fn serialized_space(&self) -> Result<String, Error> {
+        match &self.space {
+            Value::String(serialized) => Ok(serialized.clone()),
+            space => Ok(serde_json::to_string(space)?),
+        }
+    }
+}
+
+/// Where [`Spaces::import`] puts a definition.
+#[derive(Debug, Clone, Copy)]
+pub enum ImportTarget<'a> {
+    /// Create a new space, optionally in a workspace folder.
+    New { parent_path: Option<&'a str> },
+    /// Replace the configuration of an existing space.
+    Existing(&'a str),
+}
+
+impl Spaces {
+    /// Snapshot a space's configuration.
+    pub async fn export(&self, space_id: &str) -> Result<SpaceDefinition, Error> {
+        let space = self.get_serialized(space_id).await?;
+        let serialized = space.serialized_space.ok_or_else(|| {
+            Error::Other(format!(
+                "space {} has no serialized configuration",
+                space_id
+            ))
+        })?;
+        Ok(SpaceDefinition {
+            version: SPACE_DEFINITION_VERSION,
+            title: space.title,
+            description: space.description,
+            warehouse_id: space.warehouse_id,
+            space: serde_json::from_str(&serialized).unwrap_or(Value::String(serialized)),
+        })
+    }
+
+    /// Create or update a space from a definition.
+    pub async fn import(
+        &self,
+        definition: &SpaceDefinition,
+        target: ImportTarget<'_>,
+    ) -> Result<Space, Error> {
+        let serialized_space = definition.serialized_space()?;
+        match target {
+            ImportTarget::New { parent_path } => {
+                let request = CreateSpaceRequest {
+                    serialized_space,
+                    warehouse_id: definition.warehouse_id.clone(),
+                    title: definition.title.clone(),
+                    description: definition.description.clone(),
+                    parent_path: parent_path.map(str::to_string),
+                };
+                self.create(&request).await
+            }
+            ImportTarget::Existing(space_id) => {
+                let request = UpdateSpaceRequest {
+                    serialized_space: Some(serialized_space),
+                    warehouse_id: definition.warehouse_id.clone(),
+                    title: definition.title.clone(),
+                    description: definition.description.clone(),
+                };
+                self.update(space_id, &request).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use databricks_core::transport::mock::MockTransport;
+    use databricks_core::Client;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let serialized = r#"{"instructions":"Use fiscal years","tables":["sales.orders"]}"#;
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                "/api/2.0/genie/spaces/s1",
+                200,
+                json!({
+                    "space_id": "s1",
+                    "title": "Sales",
+                    "warehouse_id": "wh1",
+                    "serialized_space": serialized,
+                }),
+            )
+            .on(
+                "POST",
+                "/api/2.0/genie/spaces",
+                200,
+                json!({"space_id": "s2", "title": "Sales"}),
+            );
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        let spaces = Spaces::new(client);
+
+        let definition = spaces.export("s1").await.unwrap();
+        assert_eq!(definition.space["instructions"], "Use fiscal years");
+        let mut definition = SpaceDefinition::from_json(&definition.to_json().unwrap()).unwrap();
+        definition.warehouse_id = Some("wh2".into());
+
+        let space = spaces
+            .import(
+                &definition,
+                ImportTarget::New {
+                    parent_path: Some("/Shared"),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(space.space_id, "s2");
+
+        let requests = mock.requests();
+        assert_eq!(
+            requests[0].path,
+            "/api/2.0/genie/spaces/s1?include_serialized_space=true"
+        );
+        let body = requests[1].body.as_ref().unwrap();
+        assert_eq!(body["serialized_space"], serialized);
+        assert_eq!(body["warehouse_id"], "wh2");
+        assert_eq!(body["title"], "Sales");
+        assert_eq!(body["parent_path"], "/Shared");
+    }
+
+    #[test]
+    fn test_reject_newer_version() {
+        let json = json!({"version": SPACE_DEFINITION_VERSION + 1, "space": {}}).to_string();
+        let error = SpaceDefinition::from_json(&json).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unsupported space definition version"));
+    }
+}
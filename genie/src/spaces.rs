@@ -24,6 +24,14 @@ impl Spaces {
         self.client.get(&path).await
     }
 
+    /// Get a Genie space including its `serialized_space` configuration.
+    pub async fn get_serialized(&self, space_id: &str) -> Result<Space, Error> {
+        let path = format!("{}/{}", PATH, space_id);
+        self.client
+            .get_with_query(&path, &[("include_serialized_space", "true")])
+            .await
+    }
+
     /// Create a new Genie space.
     pub async fn create(&self, request: &CreateSpaceRequest) -> Result<Space, Error> {
         self.client.post(PATH, request).await
@@ -0,0 +1,356 @@
+use crate::conversations::Conversations;
+use crate::types::{ConversationSummary, FeedbackRating, Message, Status, TextAttachmentPurpose};
+use databricks_core::{Client, Error};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// File format of a [`Transcript`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    Markdown,
+    Json,
+}
+
+impl TranscriptFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Markdown => "md",
+            TranscriptFormat::Json => "json",
+        }
+    }
+}
+
+/// A Genie conversation, flattened for archiving.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transcript {
+    pub space_id: String,
+    pub conversation_id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub created_timestamp: Option<i64>,
+    pub messages: Vec<TranscriptMessage>,
+}
+
+/// A question and Genie's response to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptMessage {
+    pub message_id: String,
+    #[serde(default)]
+    pub created_timestamp: Option<i64>,
+    pub question: String,
+    #[serde(default)]
+    pub status: Option<Status>,
+    /// Text written by Genie.
+    #[serde(default)]
+    pub responses: Vec<String>,
+    /// Clarifying questions Genie asked instead of answering.
+    #[serde(default)]
+    pub follow_up_questions: Vec<String>,
+    #[serde(default)]
+    pub queries: Vec<TranscriptQuery>,
+    #[serde(default)]
+    pub suggested_questions: Vec<String>,
+    #[serde(default)]
+    pub feedback: Option<FeedbackRating>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptQuery {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub sql: String,
+    #[serde(default)]
+    pub row_count: Option<i64>,
+}
+
+impl From<&Message> for TranscriptMessage {
+    fn from(message: &Message) -> Self {
+        let mut transcript = Self {
+            message_id: message.message_id.clone(),
+            created_timestamp: message.created_timestamp,
+            question: message.content.clone(),
+            status: message.status,
+            responses: Vec::new(),
+            follow_up_questions: Vec::new(),
+            queries: Vec::new(),
+            suggested_questions: Vec::new(),
+            feedback: message.feedback.as_ref().and_then(|f| f.rating),
+            error: message.error.as_ref().and_then(|e| e.message.clone()),
+        };
+
+        for attachment in &message.attachments {
+            if let Some(text) = &attachment.text {
+                if let Some(content) = text.content.clone().filter(|c| !c.is_empty()) {
+                    match text.purpose {
+                        Some(TextAttachmentPurpose::FollowUpQuestion) => {
+                            transcript.follow_up_questions.push(content)
+                        }
+                        None => transcript.responses.push(content),
+                    }
+                }
+            }
+            if let Some(query) = &attachment.query {
+                if let Some(sql) = &query.query {
+                    transcript.queries.push(TranscriptQuery {
+                        title: query.title.clone(),
+                        description: query.description.clone(),
+                        sql: sql.clone(),
+                        row_count: query
+                            .query_result_metadata
+                            .as_ref()
+                            .and_then(|m| m.row_count),
+                    });
+                }
+            }
+            if let Some(suggestions) = &attachment.suggested_questions {
+                transcript
+                    .suggested_questions
+                    .extend(suggestions.questions.iter().cloned());
+            }
+        }
+        transcript
+    }
+}
+
+impl Transcript {
+    pub fn render(&self, format: TranscriptFormat) -> Result<String, Error> {
+        match format {
+            TranscriptFormat::Markdown => Ok(self.to_markdown()),
+            TranscriptFormat::Json => Ok(serde_json::to_string_pretty(self)? + "\n"),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let title = self.title.as_deref().unwrap_or(&self.conversation_id);
+        let _ = writeln!(out, "# {}\n", title);
+        let _ = writeln!(out, "- Space: `{}`", self.space_id);
+        let _ = writeln!(out, "- Conversation: `{}`", self.conversation_id);
+
+        for message in &self.messages {
+            let _ = writeln!(out, "\n---\n\n**User:** {}\n", message.question);
+            for response in &message.responses {
+                let _ = writeln!(out, "**Genie:** {}\n", response);
+            }
+            for question in &message.follow_up_questions {
+                let _ = writeln!(out, "**Genie asks:** {}\n", question);
+            }
+            for query in &message.queries {
+                if let Some(title) = &query.title {
+                    let _ = writeln!(out, "*{}*\n", title);
+                }
+                if let Some(description) = &query.description {
+                    let _ = writeln!(out, "{}\n", description);
+                }
+                let _ = writeln!(out, "```sql\n{}\n```\n", query.sql.trim_end());
+                if let Some(rows) = query.row_count {
+                    let _ = writeln!(out, "{} rows\n", rows);
+                }
+            }
+            if !message.suggested_questions.is_empty() {
+                let _ = writeln!(out, "Suggested questions:\n");
+                for question in &message.suggested_questions {
+                    let _ = writeln!(out, "- {}", question);
+                }
+                out.push('\n');
+            }
+            if let Some(error) = &message.error {
+                let _ = writeln!(out, "**Error:** {}\n", error);
+            }
+            if let Some(rating) = message.feedback.filter(|r| *r != FeedbackRating::None) {
+                let _ = writeln!(out, "Feedback: {:?}\n", rating);
+            }
+        }
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+        out
+    }
+
+    /// Write to `<conversation_id>.<ext>` in `dir`, returning the path.
+    pub fn write(&self, dir: impl AsRef<Path>, format: TranscriptFormat) -> Result<PathBuf, Error> {
+        let path = dir
+            .as_ref()
+            .join(format!("{}.{}", self.conversation_id, format.extension()));
+        std::fs::write(&path, self.render(format)?)
+            .map_err(|e| Error::Other(format!("failed to write {}: {}", path.display(), e)))?;
+        Ok(path)
+    }
+}
+
+/// Archives the conversations of a Genie space as transcripts.
+///
+/// Only the caller's conversations are archived unless
+/// [`ConversationExporter::include_all`] is set.
+///
+/// ```no_run
+/// # async fn run(client: databricks_core::Client) -> Result<(), databricks_core::Error> {
+/// use databricks_genie::{ConversationExporter, TranscriptFormat};
+///
+/// let paths = ConversationExporter::new(client, "space-id")
+///     .include_all(true)
+///     .export_to_dir("archive", TranscriptFormat::Markdown)
+///     .await?;
+/// println!("archived {} conversations", paths.len());
+/// # Ok(())
+/// # }
+/// ```
+pub struct ConversationExporter {
+    conversations: Conversations,
+    include_all: bool,
+}
+
+impl ConversationExporter {
+    pub fn new(client: Client, space_id: impl Into<String>) -> Self {
+        Self {
+            conversations: Conversations::new(client, space_id),
+            include_all: false,
+        }
+    }
+
+    /// Archive every user's conversations rather than only the caller's
+    /// (default false). Requires CAN MANAGE on the space.
+    pub fn include_all(mut self, include_all: bool) -> Self {
+        self.include_all = include_all;
+        self
+    }
+
+    /// Fetch the transcript of one conversation.
+    pub async fn transcript(&self, conversation_id: &str) -> Result<Transcript, Error> {
+        let messages = self.conversations.list_messages(conversation_id).await?;
+        Ok(Transcript {
+            space_id: self.conversations.space_id().to_string(),
+            conversation_id: conversation_id.to_string(),
+            title: None,
+            created_timestamp: None,
+            messages: messages.iter().map(TranscriptMessage::from).collect(),
+        })
+    }
+
+    /// Write a transcript of each conversation to `dir`, one file per
+    /// conversation.
+    pub async fn export_to_dir(
+        &self,
+        dir: impl AsRef<Path>,
+        format: TranscriptFormat,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let mut paths = Vec::new();
+        let mut conversations = self.conversations.list_all_conversations(self.include_all);
+        while let Some(summary) = conversations.next().await {
+            let ConversationSummary {
+                conversation_id,
+                created_timestamp,
+                title,
+            } = summary?;
+            let mut transcript = self.transcript(&conversation_id).await?;
+            transcript.title = Some(title);
+            transcript.created_timestamp = Some(created_timestamp);
+            paths.push(transcript.write(dir.as_ref(), format)?);
+        }
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_transcript() {
+        let message: Message = serde_json::from_value(serde_json::json!({
+            "message_id": "m1",
+            "space_id": "s1",
+            "conversation_id": "c1",
+            "content": "Top products?",
+            "status": "COMPLETED",
+            "feedback": {"rating": "POSITIVE"},
+            "attachments": [
+                {"text": {"content": "Here are the top products."}},
+                {"query": {"title": "Top products", "query": "SELECT 1\n"}},
+                {"suggested_questions": {"questions": ["By region?"]}},
+            ],
+        }))
+        .unwrap();
+
+        let transcript = Transcript {
+            space_id: "s1".into(),
+            conversation_id: "c1".into(),
+            title: Some("Sales".into()),
+            created_timestamp: None,
+            messages: vec![TranscriptMessage::from(&message)],
+        };
+        assert_eq!(
+            transcript.to_markdown(),
+            "# Sales\n\n- Space: `s1`\n- Conversation: `c1`\n\n---\n\n\
+             **User:** Top products?\n\n\
+             **Genie:** Here are the top products.\n\n\
+             *Top products*\n\n```sql\nSELECT 1\n```\n\n\
+             Suggested questions:\n\n- By region?\n\n\
+             Feedback: Positive\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_all_conversations() {
+        use databricks_core::transport::mock::MockTransport;
+        use serde_json::json;
+
+        let conversations = "/api/2.0/genie/spaces/s1/conversations";
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                conversations,
+                200,
+                json!({
+                    "conversations": [{"conversation_id": "c1", "created_timestamp": 1, "title": "One"}],
+                    "next_page_token": "a+b/c==",
+                }),
+            )
+            .on(
+                "GET",
+                conversations,
+                200,
+                json!({"conversations": [{"conversation_id": "c2", "created_timestamp": 2, "title": "Two"}]}),
+            )
+            .on("GET", &format!("{}/c1/messages", conversations), 200, json!({"messages": []}))
+            .on("GET", &format!("{}/c2/messages", conversations), 200, json!({"messages": []}));
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("genie-export-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let paths = ConversationExporter::new(client, "s1")
+            .include_all(true)
+            .export_to_dir(&dir, TranscriptFormat::Json)
+            .await
+            .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(paths, [dir.join("c1.json"), dir.join("c2.json")]);
+        let listed: Vec<_> = mock
+            .requests()
+            .into_iter()
+            .map(|r| r.path)
+            .filter(|p| p.starts_with(&format!("{}?", conversations)))
+            .collect();
+        assert_eq!(
+            listed,
+            [
+                format!("{}?include_all=true", conversations),
+                format!(
+                    "{}?page_token=a%2Bb%2Fc%3D%3D&include_all=true",
+                    conversations
+                ),
+            ]
+        );
+    }
+}
@@ -69,6 +69,14 @@ pub struct Message {
     pub error: Option<MessageError>,
     #[serde(default)]
     pub created_timestamp: Option<i64>,
+    #[serde(default)]
+    pub feedback: Option<MessageFeedback>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageFeedback {
+    #[serde(default)]
+    pub rating: Option<FeedbackRating>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
@@ -207,64 +215,52 @@ pub struct ListMessagesResponse {
 // Query execution types
 // ============================================================================
 
+/// Statement result of a query attachment.
+///
+/// Genie runs generated queries through the Statement Execution API, so results
+/// decode, page through chunks and export like any other statement.
+pub type StatementResponse = databricks_sql::Response;
+
 /// Response from executing a query attachment
 #[derive(Debug, Clone, Deserialize)]
 pub struct ExecuteQueryResponse {
-    /// The SQL statement response (same as sql::Response)
-    #[serde(default)]
+    #[serde(default, deserialize_with = "statement_response")]
     pub statement_response: Option<StatementResponse>,
 }
 
-/// Simplified statement response for Genie query results
-#[derive(Debug, Clone, Deserialize)]
-pub struct StatementResponse {
-    #[serde(default)]
-    pub status: Option<StatementStatus>,
-    #[serde(default)]
-    pub manifest: Option<ResultManifest>,
-    #[serde(default)]
-    pub result: Option<ResultData>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-pub struct StatementStatus {
-    #[serde(default)]
-    pub state: Option<String>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-pub struct ResultManifest {
-    #[serde(default)]
-    pub schema: Option<ResultSchema>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-pub struct ResultSchema {
-    #[serde(default)]
-    pub columns: Vec<ColumnInfo>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-pub struct ColumnInfo {
-    pub name: String,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-pub struct ResultData {
-    #[serde(default)]
-    pub data_array: Vec<ResultRow>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-pub struct ResultRow {
-    #[serde(default)]
-    pub values: Vec<ResultValue>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-pub struct ResultValue {
-    #[serde(default)]
-    pub string_value: Option<String>,
+/// Deserialize a statement response, accepting rows in the typed
+/// `{"values": [{"string_value": ..}]}` form as well as plain arrays.
+fn statement_response<'de, D>(deserializer: D) -> Result<Option<StatementResponse>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error as _;
+    use serde_json::Value;
+
+    let Some(mut value) = Option::<Value>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    if let Some(rows) = value
+        .pointer_mut("/result/data_array")
+        .and_then(Value::as_array_mut)
+    {
+        for row in rows.iter_mut() {
+            if let Some(values) = row.get_mut("values").map(Value::take) {
+                *row = values
+                    .as_array()
+                    .map(|values| {
+                        values
+                            .iter()
+                            .map(|v| v.get("string_value").cloned().unwrap_or(Value::Null))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+            }
+        }
+    }
+    serde_json::from_value(value)
+        .map(Some)
+        .map_err(D::Error::custom)
 }
 
 // ============================================================================
@@ -275,6 +271,10 @@ pub struct ResultValue {
 pub struct CreateSpaceRequest {
     pub serialized_space: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub warehouse_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_path: Option<String>,
@@ -285,6 +285,10 @@ pub struct UpdateSpaceRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub serialized_space: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub warehouse_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 }
 
@@ -323,3 +327,41 @@ pub enum FeedbackRating {
 pub struct SendMessageFeedbackRequest {
     pub rating: FeedbackRating,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_query_result_rows_decode_as_statement_response() {
+        use databricks_core::transport::mock::MockTransport;
+        use futures::TryStreamExt;
+
+        let response: ExecuteQueryResponse = serde_json::from_value(serde_json::json!({
+            "statement_response": {
+                "statement_id": "st1",
+                "status": {"state": "SUCCEEDED"},
+                "manifest": {"schema": {"columns": [
+                    {"name": "n", "type_name": "INT"},
+                    {"name": "s", "type_name": "STRING"}
+                ]}},
+                "result": {"data_array": [{"values": [{"string_value": "7"}, {}]}]}
+            }
+        }))
+        .unwrap();
+
+        let client = databricks_core::Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(MockTransport::new())
+            .build()
+            .unwrap();
+        let response = response.statement_response.unwrap();
+        let rows: Vec<(i32, Option<String>)> = response
+            .rows::<(i32, Option<String>)>(&client)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(rows, [(7, None)]);
+    }
+}
@@ -26,6 +26,23 @@ impl Groups {
         Ok(response.resources)
     }
 
+    /// One page of groups, starting at the 1-based `start_index`.
+    pub async fn list_page(
+        &self,
+        start_index: i64,
+        count: i64,
+    ) -> Result<ListGroupsResponse, Error> {
+        self.client
+            .get_with_query(
+                PATH,
+                &[
+                    ("startIndex", &start_index.to_string()),
+                    ("count", &count.to_string()),
+                ],
+            )
+            .await
+    }
+
     pub async fn update(&self, id: &str, group: &Group) -> Result<Group, Error> {
         let path = format!("{}/{}", PATH, id);
         self.client.put(&path, group).await
@@ -1,16 +1,18 @@
 mod account_access_control;
 mod current_user;
 mod groups;
+mod object_permissions;
 mod permission_migration;
 mod permissions;
 mod service_principals;
-mod types;
+pub mod types;
 mod users;
 mod workspace_assignment;
 
 pub use account_access_control::AccountAccessControl;
 pub use current_user::Me;
 pub use groups::Groups;
+pub use object_permissions::ObjectPermissions;
 pub use permission_migration::PermissionMigration;
 pub use permissions::Permissions;
 pub use service_principals::ServicePrincipals;
@@ -0,0 +1,172 @@
+use crate::types::{
+    Acl, AclDiff, AclEntry, ObjectPermissionsResponse, ObjectRef, PermissionLevel,
+    PermissionLevelDescription, PermissionLevels, SetPermissions, UpdatePermissions,
+};
+use databricks_core::{Client, Error};
+
+const PATH: &str = "/api/2.0/permissions";
+
+/// Typed Permissions API for any securable workspace object.
+///
+/// Replaces the per-service `get_permissions`/`set_permissions` methods, now
+/// deprecated, with one API keyed by [`ObjectRef`].
+pub struct ObjectPermissions {
+    client: Client,
+}
+
+impl ObjectPermissions {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    fn path(object: &ObjectRef) -> String {
+        format!("{}/{}/{}", PATH, object.object_type(), object.object_id())
+    }
+
+    /// Get the ACL of an object, including inherited grants.
+    pub async fn get(&self, object: &ObjectRef) -> Result<Acl, Error> {
+        let response: ObjectPermissionsResponse = self.client.get(&Self::path(object)).await?;
+        Ok(Acl::from_response(object.clone(), response))
+    }
+
+    /// Get the permission levels the object supports.
+    pub async fn levels(
+        &self,
+        object: &ObjectRef,
+    ) -> Result<Vec<PermissionLevelDescription>, Error> {
+        let path = format!("{}/permissionLevels", Self::path(object));
+        let response: PermissionLevels = self.client.get(&path).await?;
+        Ok(response
+            .permission_levels
+            .into_iter()
+            .filter_map(|d| {
+                Some(PermissionLevelDescription {
+                    level: PermissionLevel::parse(&d.permission_level?),
+                    description: d.description,
+                })
+            })
+            .collect())
+    }
+
+    /// Replace the direct ACL of an object.
+    pub async fn set(&self, object: &ObjectRef, entries: &[AclEntry]) -> Result<Acl, Error> {
+        let request = SetPermissions {
+            access_control_list: entries.iter().map(AclEntry::to_request).collect(),
+        };
+        let response: ObjectPermissionsResponse =
+            self.client.put(&Self::path(object), &request).await?;
+        Ok(Acl::from_response(object.clone(), response))
+    }
+
+    /// Add entries to the direct ACL of an object, keeping existing ones.
+    pub async fn update(&self, object: &ObjectRef, entries: &[AclEntry]) -> Result<Acl, Error> {
+        let request = UpdatePermissions {
+            access_control_list: entries.iter().map(AclEntry::to_request).collect(),
+        };
+        let response: ObjectPermissionsResponse =
+            self.client.patch(&Self::path(object), &request).await?;
+        Ok(Acl::from_response(object.clone(), response))
+    }
+
+    /// Compute the changes `ensure` would make, without applying them.
+    pub async fn diff(&self, object: &ObjectRef, desired: &[AclEntry]) -> Result<AclDiff, Error> {
+        let current = self.get(object).await?.direct();
+        Ok(AclDiff::between(&current, &with_owner(&current, desired)))
+    }
+
+    /// Make the direct ACL of an object equal to `desired`.
+    ///
+    /// Only writes when the ACL differs, so repeated calls are no-ops. The
+    /// current `IS_OWNER` entry is kept unless `desired` names an owner, since
+    /// objects such as jobs and pipelines cannot be left ownerless.
+    ///
+    /// Returns the changes that were applied.
+    pub async fn ensure(&self, object: &ObjectRef, desired: &[AclEntry]) -> Result<AclDiff, Error> {
+        let current = self.get(object).await?.direct();
+        let desired = with_owner(&current, desired);
+        let diff = AclDiff::between(&current, &desired);
+        if !diff.is_empty() {
+            self.set(object, &desired).await?;
+        }
+        Ok(diff)
+    }
+}
+
+/// `desired`, plus the current owner if `desired` does not name one.
+fn with_owner(current: &[AclEntry], desired: &[AclEntry]) -> Vec<AclEntry> {
+    let mut desired = desired.to_vec();
+    if !desired.iter().any(|e| e.level == PermissionLevel::IsOwner) {
+        desired.extend(
+            current
+                .iter()
+                .filter(|e| e.level == PermissionLevel::IsOwner)
+                .cloned(),
+        );
+    }
+    desired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AccessControlEntry, Permission};
+
+    fn grant(level: &str, inherited: bool) -> Permission {
+        Permission {
+            permission_level: level.into(),
+            inherited: Some(inherited),
+            inherited_from_object: None,
+        }
+    }
+
+    #[test]
+    fn test_direct_acl_skips_inherited() {
+        let response = ObjectPermissionsResponse {
+            object_id: Some("/jobs/1".into()),
+            object_type: Some("job".into()),
+            access_control_list: Some(vec![
+                AccessControlEntry {
+                    user_name: Some("a@example.com".into()),
+                    group_name: None,
+                    service_principal_name: None,
+                    all_permissions: Some(vec![grant("IS_OWNER", false)]),
+                },
+                AccessControlEntry {
+                    user_name: None,
+                    group_name: Some("admins".into()),
+                    service_principal_name: None,
+                    all_permissions: Some(vec![grant("CAN_MANAGE", true)]),
+                },
+            ]),
+        };
+
+        let acl = Acl::from_response(ObjectRef::Job("1".into()), response);
+        assert_eq!(acl.grants.len(), 2);
+        assert_eq!(
+            acl.direct(),
+            vec![AclEntry::user("a@example.com", PermissionLevel::IsOwner)]
+        );
+    }
+
+    #[test]
+    fn test_ensure_plan_keeps_owner() {
+        let current = vec![
+            AclEntry::user("a@example.com", PermissionLevel::IsOwner),
+            AclEntry::group("users", PermissionLevel::CanManage),
+        ];
+        let desired = vec![AclEntry::group("users", PermissionLevel::CanView)];
+
+        let diff = AclDiff::between(&current, &with_owner(&current, &desired));
+        assert_eq!(
+            diff.added,
+            vec![AclEntry::group("users", PermissionLevel::CanView)]
+        );
+        assert_eq!(
+            diff.removed,
+            vec![AclEntry::group("users", PermissionLevel::CanManage)]
+        );
+
+        let unchanged = AclDiff::between(&current, &with_owner(&current, &current[1..]));
+        assert!(unchanged.is_empty());
+    }
+}
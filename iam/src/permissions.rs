@@ -1,4 +1,6 @@
-use crate::types::{ObjectPermissions, PermissionLevels, SetPermissions, UpdatePermissions};
+use crate::types::{
+    ObjectPermissionsResponse, PermissionLevels, SetPermissions, UpdatePermissions,
+};
 use databricks_core::{Client, Error};
 
 const PATH: &str = "/api/2.0/permissions";
@@ -14,11 +16,13 @@ impl Permissions {
 
     /// Get permissions for an object.
     /// `object_type` examples: "clusters", "jobs", "sql/warehouses", "notebooks"
+    ///
+    /// See [`crate::ObjectPermissions`] for a typed alternative.
     pub async fn get(
         &self,
         object_type: &str,
         object_id: &str,
-    ) -> Result<ObjectPermissions, Error> {
+    ) -> Result<ObjectPermissionsResponse, Error> {
         let path = format!("{}/{}/{}", PATH, object_type, object_id);
         self.client.get(&path).await
     }
@@ -39,7 +43,7 @@ impl Permissions {
         object_type: &str,
         object_id: &str,
         request: &SetPermissions,
-    ) -> Result<ObjectPermissions, Error> {
+    ) -> Result<ObjectPermissionsResponse, Error> {
         let path = format!("{}/{}/{}", PATH, object_type, object_id);
         self.client.put(&path, request).await
     }
@@ -50,7 +54,7 @@ impl Permissions {
         object_type: &str,
         object_id: &str,
         request: &UpdatePermissions,
-    ) -> Result<ObjectPermissions, Error> {
+    ) -> Result<ObjectPermissionsResponse, Error> {
         let path = format!("{}/{}/{}", PATH, object_type, object_id);
         self.client.patch(&path, request).await
     }
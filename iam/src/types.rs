@@ -129,7 +129,7 @@ pub struct ListServicePrincipalsResponse {
 // ============================================================================
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ObjectPermissions {
+pub struct ObjectPermissionsResponse {
     #[serde(default)]
     pub object_id: Option<String>,
     #[serde(default)]
@@ -138,6 +138,13 @@ pub struct ObjectPermissions {
     pub access_control_list: Option<Vec<AccessControlEntry>>,
 }
 
+/// Former name of [`ObjectPermissionsResponse`].
+///
+/// `databricks_iam::ObjectPermissions` is now the typed permissions service,
+/// so this alias is only reachable as `databricks_iam::types::ObjectPermissions`.
+#[deprecated(note = "renamed to `ObjectPermissionsResponse`")]
+pub type ObjectPermissions = ObjectPermissionsResponse;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccessControlEntry {
     #[serde(default)]
@@ -194,6 +201,352 @@ pub struct PermissionsDescription {
     pub description: Option<String>,
 }
 
+// ============================================================================
+// Typed object permissions
+// ============================================================================
+
+/// A securable workspace object addressed through the Permissions API.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "type", content = "id", rename_all = "snake_case")]
+pub enum ObjectRef {
+    Cluster(String),
+    ClusterPolicy(String),
+    InstancePool(String),
+    Job(String),
+    Pipeline(String),
+    Warehouse(String),
+    Notebook(String),
+    Directory(String),
+    File(String),
+    Repo(String),
+    ServingEndpoint(String),
+    App(String),
+    Experiment(String),
+    RegisteredModel(String),
+    Query(String),
+    Alert(String),
+    Dashboard(String),
+    /// Token usage permissions (`authorization/tokens`).
+    Tokens,
+    /// Password login permissions (`authorization/passwords`).
+    Passwords,
+}
+
+impl ObjectRef {
+    /// The `request_object_type` path segment of the Permissions API.
+    pub fn object_type(&self) -> &'static str {
+        match self {
+            ObjectRef::Cluster(_) => "clusters",
+            ObjectRef::ClusterPolicy(_) => "cluster-policies",
+            ObjectRef::InstancePool(_) => "instance-pools",
+            ObjectRef::Job(_) => "jobs",
+            ObjectRef::Pipeline(_) => "pipelines",
+            ObjectRef::Warehouse(_) => "warehouses",
+            ObjectRef::Notebook(_) => "notebooks",
+            ObjectRef::Directory(_) => "directories",
+            ObjectRef::File(_) => "files",
+            ObjectRef::Repo(_) => "repos",
+            ObjectRef::ServingEndpoint(_) => "serving-endpoints",
+            ObjectRef::App(_) => "apps",
+            ObjectRef::Experiment(_) => "experiments",
+            ObjectRef::RegisteredModel(_) => "registered-models",
+            ObjectRef::Query(_) => "queries",
+            ObjectRef::Alert(_) => "alerts",
+            ObjectRef::Dashboard(_) => "dashboards",
+            ObjectRef::Tokens | ObjectRef::Passwords => "authorization",
+        }
+    }
+
+    /// The `request_object_id` path segment of the Permissions API.
+    pub fn object_id(&self) -> &str {
+        match self {
+            ObjectRef::Cluster(id)
+            | ObjectRef::ClusterPolicy(id)
+            | ObjectRef::InstancePool(id)
+            | ObjectRef::Job(id)
+            | ObjectRef::Pipeline(id)
+            | ObjectRef::Warehouse(id)
+            | ObjectRef::Notebook(id)
+            | ObjectRef::Directory(id)
+            | ObjectRef::File(id)
+            | ObjectRef::Repo(id)
+            | ObjectRef::ServingEndpoint(id)
+            | ObjectRef::App(id)
+            | ObjectRef::Experiment(id)
+            | ObjectRef::RegisteredModel(id)
+            | ObjectRef::Query(id)
+            | ObjectRef::Alert(id)
+            | ObjectRef::Dashboard(id) => id,
+            ObjectRef::Tokens => "tokens",
+            ObjectRef::Passwords => "passwords",
+        }
+    }
+}
+
+impl std::fmt::Display for ObjectRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.object_type(), self.object_id())
+    }
+}
+
+/// The user, group or service principal an ACL entry applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "type", content = "name", rename_all = "snake_case")]
+pub enum Principal {
+    User(String),
+    Group(String),
+    ServicePrincipal(String),
+}
+
+impl Principal {
+    pub fn name(&self) -> &str {
+        match self {
+            Principal::User(name) | Principal::Group(name) | Principal::ServicePrincipal(name) => {
+                name
+            }
+        }
+    }
+
+    fn from_names(
+        user_name: Option<String>,
+        group_name: Option<String>,
+        service_principal_name: Option<String>,
+    ) -> Option<Self> {
+        user_name
+            .map(Principal::User)
+            .or_else(|| group_name.map(Principal::Group))
+            .or_else(|| service_principal_name.map(Principal::ServicePrincipal))
+    }
+}
+
+impl std::fmt::Display for Principal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Principal::User(name) => write!(f, "user:{}", name),
+            Principal::Group(name) => write!(f, "group:{}", name),
+            Principal::ServicePrincipal(name) => write!(f, "service_principal:{}", name),
+        }
+    }
+}
+
+/// Permission level shared by all object types.
+///
+/// Levels not known to this SDK are preserved in [`PermissionLevel::Other`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PermissionLevel {
+    IsOwner,
+    CanManage,
+    CanManageRun,
+    CanManageProductionVersions,
+    CanManageStagingVersions,
+    CanRestart,
+    CanAttachTo,
+    CanEdit,
+    CanRun,
+    CanRead,
+    CanView,
+    CanQuery,
+    CanMonitor,
+    CanUse,
+    Other(String),
+}
+
+impl PermissionLevel {
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "IS_OWNER" => PermissionLevel::IsOwner,
+            "CAN_MANAGE" => PermissionLevel::CanManage,
+            "CAN_MANAGE_RUN" => PermissionLevel::CanManageRun,
+            "CAN_MANAGE_PRODUCTION_VERSIONS" => PermissionLevel::CanManageProductionVersions,
+            "CAN_MANAGE_STAGING_VERSIONS" => PermissionLevel::CanManageStagingVersions,
+            "CAN_RESTART" => PermissionLevel::CanRestart,
+            "CAN_ATTACH_TO" => PermissionLevel::CanAttachTo,
+            "CAN_EDIT" => PermissionLevel::CanEdit,
+            "CAN_RUN" => PermissionLevel::CanRun,
+            "CAN_READ" => PermissionLevel::CanRead,
+            "CAN_VIEW" => PermissionLevel::CanView,
+            "CAN_QUERY" => PermissionLevel::CanQuery,
+            "CAN_MONITOR" => PermissionLevel::CanMonitor,
+            "CAN_USE" => PermissionLevel::CanUse,
+            other => PermissionLevel::Other(other.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            PermissionLevel::IsOwner => "IS_OWNER",
+            PermissionLevel::CanManage => "CAN_MANAGE",
+            PermissionLevel::CanManageRun => "CAN_MANAGE_RUN",
+            PermissionLevel::CanManageProductionVersions => "CAN_MANAGE_PRODUCTION_VERSIONS",
+            PermissionLevel::CanManageStagingVersions => "CAN_MANAGE_STAGING_VERSIONS",
+            PermissionLevel::CanRestart => "CAN_RESTART",
+            PermissionLevel::CanAttachTo => "CAN_ATTACH_TO",
+            PermissionLevel::CanEdit => "CAN_EDIT",
+            PermissionLevel::CanRun => "CAN_RUN",
+            PermissionLevel::CanRead => "CAN_READ",
+            PermissionLevel::CanView => "CAN_VIEW",
+            PermissionLevel::CanQuery => "CAN_QUERY",
+            PermissionLevel::CanMonitor => "CAN_MONITOR",
+            PermissionLevel::CanUse => "CAN_USE",
+            PermissionLevel::Other(s) => s,
+        }
+    }
+}
+
+impl std::fmt::Display for PermissionLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for PermissionLevel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for PermissionLevel {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(PermissionLevel::parse(&s))
+    }
+}
+
+/// A principal granted a permission level.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AclEntry {
+    pub principal: Principal,
+    pub level: PermissionLevel,
+}
+
+impl AclEntry {
+    pub fn new(principal: Principal, level: PermissionLevel) -> Self {
+        Self { principal, level }
+    }
+
+    pub fn user(name: impl Into<String>, level: PermissionLevel) -> Self {
+        Self::new(Principal::User(name.into()), level)
+    }
+
+    pub fn group(name: impl Into<String>, level: PermissionLevel) -> Self {
+        Self::new(Principal::Group(name.into()), level)
+    }
+
+    pub fn service_principal(name: impl Into<String>, level: PermissionLevel) -> Self {
+        Self::new(Principal::ServicePrincipal(name.into()), level)
+    }
+
+    pub(crate) fn to_request(&self) -> AccessControlRequest {
+        let (user_name, group_name, service_principal_name) = match &self.principal {
+            Principal::User(n) => (Some(n.clone()), None, None),
+            Principal::Group(n) => (None, Some(n.clone()), None),
+            Principal::ServicePrincipal(n) => (None, None, Some(n.clone())),
+        };
+        AccessControlRequest {
+            user_name,
+            group_name,
+            service_principal_name,
+            permission_level: self.level.as_str().to_string(),
+        }
+    }
+}
+
+/// An ACL entry as reported by the server, including inheritance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AclGrant {
+    pub principal: Principal,
+    pub level: PermissionLevel,
+    /// Whether the grant is inherited from a parent object (not settable here).
+    pub inherited: bool,
+    #[serde(default)]
+    pub inherited_from: Vec<String>,
+}
+
+/// The access control list of an object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Acl {
+    pub object: ObjectRef,
+    pub grants: Vec<AclGrant>,
+}
+
+impl Acl {
+    pub(crate) fn from_response(object: ObjectRef, response: ObjectPermissionsResponse) -> Self {
+        let grants = response
+            .access_control_list
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|entry| {
+                let principal = Principal::from_names(
+                    entry.user_name,
+                    entry.group_name,
+                    entry.service_principal_name,
+                )?;
+                Some(
+                    entry
+                        .all_permissions
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(move |p| AclGrant {
+                            principal: principal.clone(),
+                            level: PermissionLevel::parse(&p.permission_level),
+                            inherited: p.inherited.unwrap_or(false),
+                            inherited_from: p.inherited_from_object.unwrap_or_default(),
+                        }),
+                )
+            })
+            .flatten()
+            .collect();
+
+        Self { object, grants }
+    }
+
+    /// Entries set directly on the object, i.e. the part of the ACL that
+    /// `set`/`update` can change.
+    pub fn direct(&self) -> Vec<AclEntry> {
+        let mut entries: Vec<AclEntry> = self
+            .grants
+            .iter()
+            .filter(|g| !g.inherited)
+            .map(|g| AclEntry::new(g.principal.clone(), g.level.clone()))
+            .collect();
+        entries.sort();
+        entries.dedup();
+        entries
+    }
+}
+
+/// Changes needed to move an object's direct ACL to a desired state.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AclDiff {
+    /// Entries in the desired ACL but not the current one.
+    pub added: Vec<AclEntry>,
+    /// Entries in the current ACL but not the desired one.
+    pub removed: Vec<AclEntry>,
+}
+
+impl AclDiff {
+    /// Compare direct entries; ordering and duplicates are ignored.
+    pub fn between(current: &[AclEntry], desired: &[AclEntry]) -> Self {
+        let current: std::collections::BTreeSet<&AclEntry> = current.iter().collect();
+        let desired: std::collections::BTreeSet<&AclEntry> = desired.iter().collect();
+        Self {
+            added: desired.difference(&current).map(|e| (*e).clone()).collect(),
+            removed: current.difference(&desired).map(|e| (*e).clone()).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// A permission level an object type supports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionLevelDescription {
+    pub level: PermissionLevel,
+    pub description: Option<String>,
+}
+
 // ============================================================================
 // Current User types
 // ============================================================================
@@ -30,6 +30,23 @@ impl Users {
         Ok(response.resources)
     }
 
+    /// One page of users, starting at the 1-based `start_index`.
+    pub async fn list_page(
+        &self,
+        start_index: i64,
+        count: i64,
+    ) -> Result<ListUsersResponse, Error> {
+        self.client
+            .get_with_query(
+                PATH,
+                &[
+                    ("startIndex", &start_index.to_string()),
+                    ("count", &count.to_string()),
+                ],
+            )
+            .await
+    }
+
     pub async fn update(&self, id: &str, user: &User) -> Result<User, Error> {
         let path = format!("{}/{}", PATH, id);
         self.client.put(&path, user).await
@@ -47,11 +64,13 @@ impl Users {
     }
 
     /// Get password permissions.
+    #[deprecated(note = "use `ObjectPermissions`")]
     pub async fn get_permissions(&self) -> Result<PasswordPermissions, Error> {
         self.client.get(PASSWORD_PERMISSIONS_PATH).await
     }
 
     /// Get password permission levels.
+    #[deprecated(note = "use `ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
     ) -> Result<GetPasswordPermissionLevelsResponse, Error> {
@@ -60,6 +79,7 @@ impl Users {
     }
 
     /// Set password permissions (full replacement).
+    #[deprecated(note = "use `ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         request: &PasswordPermissionsRequest,
@@ -68,6 +88,7 @@ impl Users {
     }
 
     /// Update password permissions (partial update).
+    #[deprecated(note = "use `ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         request: &PasswordPermissionsRequest,
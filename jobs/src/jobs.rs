@@ -32,6 +32,18 @@ impl Jobs {
         Ok(response.jobs)
     }
 
+    /// One page of jobs; pass the previous page's `next_page_token` to
+    /// continue.
+    pub async fn list_page(&self, page_token: Option<&str>) -> Result<ListJobsResponse, Error> {
+        let mut query = vec![("limit", "100")];
+        if let Some(t) = page_token {
+            query.push(("page_token", t));
+        }
+        self.client
+            .get_with_query(&format!("{}/list", PATH), &query)
+            .await
+    }
+
     pub async fn update(&self, request: &UpdateJob) -> Result<(), Error> {
         let _: EmptyResponse = self
             .client
@@ -91,6 +103,7 @@ impl Jobs {
     }
 
     /// Get the permissions of a job.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(&self, job_id: i64) -> Result<JobPermissions, Error> {
         self.client
             .get(&format!("{}/{}", PERMISSIONS_PATH, job_id))
@@ -98,6 +111,7 @@ impl Jobs {
     }
 
     /// Get the permission levels that a user can have on a job.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         job_id: i64,
@@ -108,6 +122,7 @@ impl Jobs {
     }
 
     /// Set permissions on a job, replacing existing permissions.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         job_id: i64,
@@ -119,6 +134,7 @@ impl Jobs {
     }
 
     /// Update the permissions on a job.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         job_id: i64,
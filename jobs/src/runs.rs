@@ -2,9 +2,9 @@ use crate::types::{
     EmptyResponse, ListRunsResponse, RepairRun, RepairRunResponse, Run, RunId, RunOutput,
     SubmitRun, SubmitRunResponse,
 };
+use databricks_core::runtime::Instant;
 use databricks_core::{Client, Error};
 use std::time::Duration;
-use tokio::time::sleep;
 
 const PATH: &str = "/api/2.1/jobs/runs";
 
@@ -87,7 +87,7 @@ impl Runs {
         poll_interval: Duration,
         timeout: Duration,
     ) -> Result<Run, Error> {
-        let start = std::time::Instant::now();
+        let start = Instant::now();
         loop {
             let run = self.get(run_id).await?;
             if let Some(state) = &run.state {
@@ -100,7 +100,7 @@ impl Runs {
             if start.elapsed() > timeout {
                 return Err(Error::Timeout("Run timed out".into()));
             }
-            sleep(poll_interval).await;
+            self.client.runtime().sleep(poll_interval).await;
         }
     }
 }
@@ -178,6 +178,7 @@ impl Experiments {
     }
 
     /// Get the permissions of an experiment.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(
         &self,
         experiment_id: &str,
@@ -191,6 +192,7 @@ impl Experiments {
     }
 
     /// Get the permission levels that a user can have on an experiment.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         experiment_id: &str,
@@ -204,6 +206,7 @@ impl Experiments {
     }
 
     /// Set permissions on an experiment, replacing existing permissions.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         experiment_id: &str,
@@ -218,6 +221,7 @@ impl Experiments {
     }
 
     /// Update the permissions on an experiment.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         experiment_id: &str,
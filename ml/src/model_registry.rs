@@ -473,6 +473,7 @@ impl ModelRegistry {
     // ========================================================================
 
     /// Get the permissions of a registered model.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(
         &self,
         registered_model_id: &str,
@@ -486,6 +487,7 @@ impl ModelRegistry {
     }
 
     /// Get the permission levels that a user can have on a registered model.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         registered_model_id: &str,
@@ -499,6 +501,7 @@ impl ModelRegistry {
     }
 
     /// Set permissions on a registered model, replacing existing permissions.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         registered_model_id: &str,
@@ -516,6 +519,7 @@ impl ModelRegistry {
     }
 
     /// Update the permissions on a registered model.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         registered_model_id: &str,
@@ -157,6 +157,7 @@ impl Pipelines {
     }
 
     /// Get the permissions of a pipeline.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(&self, pipeline_id: &str) -> Result<PipelinePermissions, Error> {
         self.client
             .get(&format!("{}/{}", PERMISSIONS_PATH, pipeline_id))
@@ -164,6 +165,7 @@ impl Pipelines {
     }
 
     /// Get the permission levels that a user can have on a pipeline.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         pipeline_id: &str,
@@ -177,6 +179,7 @@ impl Pipelines {
     }
 
     /// Set permissions on a pipeline, replacing existing permissions.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         pipeline_id: &str,
@@ -188,6 +191,7 @@ impl Pipelines {
     }
 
     /// Update the permissions on a pipeline.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         pipeline_id: &str,
@@ -149,6 +149,7 @@ impl ServingEndpoints {
     }
 
     /// Get permissions for a serving endpoint.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(
         &self,
         serving_endpoint_id: &str,
@@ -162,6 +163,7 @@ impl ServingEndpoints {
     }
 
     /// Get permission levels for a serving endpoint.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         serving_endpoint_id: &str,
@@ -175,6 +177,7 @@ impl ServingEndpoints {
     }
 
     /// Set (replace) permissions for a serving endpoint.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         serving_endpoint_id: &str,
@@ -192,6 +195,7 @@ impl ServingEndpoints {
     }
 
     /// Update (patch) permissions for a serving endpoint.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         serving_endpoint_id: &str,
@@ -50,11 +50,13 @@ impl TokenManagement {
     }
 
     /// Get the permissions of all tokens.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(&self) -> Result<TokenPermissions, Error> {
         self.client.get(PERMISSIONS_PATH).await
     }
 
     /// Get permission levels that a user can have on tokens.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(&self) -> Result<GetTokenPermissionLevelsResponse, Error> {
         self.client
             .get(&format!("{}/permissionLevels", PERMISSIONS_PATH))
@@ -62,6 +64,7 @@ impl TokenManagement {
     }
 
     /// Set permissions on all tokens, replacing existing permissions.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         request: &TokenPermissionsRequest,
@@ -70,6 +73,7 @@ impl TokenManagement {
     }
 
     /// Update permissions on all tokens.
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         request: &TokenPermissionsRequest,
@@ -1,4 +1,5 @@
 use crate::alert_types::{Alert, CreateAlertRequest, ListAlertsResponse, UpdateAlertRequest};
+use databricks_core::paginator::{Page, Paginator};
 use databricks_core::{Client, Error};
 
 const PATH: &str = "/api/2.0/sql/alerts";
@@ -40,6 +41,17 @@ impl Alerts {
         self.client.get_with_query(PATH, &pairs).await
     }
 
+    /// Iterate all alerts, fetching pages on demand.
+    pub fn list_all(&self) -> Paginator<Alert> {
+        Paginator::new(self.client.clone(), |client, token| async move {
+            let response = Self::new(client).list(None, token.as_deref()).await?;
+            Ok(Page {
+                items: response.results,
+                next_token: response.next_page_token,
+            })
+        })
+    }
+
     /// Update an alert.
     pub async fn update(&self, id: &str, request: &UpdateAlertRequest) -> Result<Alert, Error> {
         let path = format!("{}/{}", PATH, id);
@@ -0,0 +1,260 @@
+use crate::external_links::Downloader;
+use crate::types::{Response, Schema};
+use crate::Statements;
+use arrow::array::{ArrayRef, RecordBatch, StringArray};
+use arrow::compute::{cast_with_options, CastOptions};
+use arrow::datatypes::{DataType, Field, Schema as ArrowSchema, SchemaRef, TimeUnit};
+use arrow::ipc::reader::StreamReader;
+use base64::Engine;
+use databricks_core::{Client, Error};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use std::sync::Arc;
+
+/// Decodes [`crate::Format::ArrowStream`] results into Arrow record batches.
+///
+/// Works for both dispositions: inline results are read from the base64
+/// `attachment` of each chunk, external-link results are fetched through a
+/// [`Downloader`].
+///
+/// ```no_run
+/// # async fn run(client: databricks_core::Client, response: databricks_sql::Response) -> Result<(), databricks_core::Error> {
+/// use futures::TryStreamExt;
+///
+/// let reader = databricks_sql::ArrowReader::new(client);
+/// let mut batches = std::pin::pin!(reader.record_batches(&response));
+/// while let Some(batch) = batches.try_next().await? {
+///     println!("{} rows", batch.num_rows());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ArrowReader {
+    statements: Statements,
+    downloader: Downloader,
+}
+
+impl ArrowReader {
+    pub fn new(client: Client) -> Self {
+        Self {
+            statements: Statements::new(client.clone()),
+            downloader: Downloader::new(client),
+        }
+    }
+
+    /// Use a configured downloader for external-link results.
+    pub fn downloader(mut self, downloader: Downloader) -> Self {
+        self.downloader = downloader;
+        self
+    }
+
+    /// Arrow schema of a statement result, mapped from its manifest.
+    ///
+    /// Available before any chunk is fetched, including for empty results.
+    pub fn schema(response: &Response) -> Result<SchemaRef, Error> {
+        response
+            .manifest
+            .as_ref()
+            .and_then(|m| m.schema.as_ref())
+            .map(schema)
+            .ok_or_else(|| Error::Other("statement result has no manifest schema".into()))
+    }
+
+    /// Stream every record batch of a succeeded statement, in chunk order.
+    pub fn record_batches<'a>(
+        &'a self,
+        response: &'a Response,
+    ) -> impl Stream<Item = Result<RecordBatch, Error>> + 'a {
+        let inline = response
+            .result
+            .as_ref()
+            .is_some_and(|r| r.attachment.is_some());
+
+        let chunks = if inline {
+            self.inline_chunks(response).boxed()
+        } else {
+            self.downloader
+                .chunks(response)
+                .map_ok(|chunk| chunk.data)
+                .boxed()
+        };
+
+        chunks
+            .and_then(|data| async move { decode(&data) })
+            .map_ok(|batches| stream::iter(batches.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Download and decode every record batch into memory.
+    pub async fn collect(&self, response: &Response) -> Result<Vec<RecordBatch>, Error> {
+        self.record_batches(response).try_collect().await
+    }
+
+    fn inline_chunks<'a>(
+        &'a self,
+        response: &'a Response,
+    ) -> impl Stream<Item = Result<Vec<u8>, Error>> + Send + 'a {
+        let total = response
+            .manifest
+            .as_ref()
+            .and_then(|m| m.total_chunk_count)
+            .unwrap_or(1);
+
+        stream::iter(0..total).then(move |index| async move {
+            let attachment = match (index, &response.result) {
+                (0, Some(result)) => result.attachment.clone(),
+                _ => {
+                    self.statements
+                        .get_result_chunk(&response.statement_id, index)
+                        .await?
+                        .attachment
+                }
+            };
+            let attachment = attachment
+                .ok_or_else(|| Error::Other(format!("chunk {} has no Arrow attachment", index)))?;
+            base64::engine::general_purpose::STANDARD
+                .decode(attachment)
+                .map_err(|e| Error::Other(format!("invalid Arrow attachment: {}", e)))
+        })
+    }
+}
+
+/// Decode one Arrow IPC stream, as returned for a single result chunk.
+pub fn decode(data: &[u8]) -> Result<Vec<RecordBatch>, Error> {
+    StreamReader::try_new(data, None)
+        .and_then(|reader| reader.collect())
+        .map_err(|e| Error::Other(format!("failed to decode Arrow result: {}", e)))
+}
+
+/// Build a record batch from `JSON_ARRAY` rows, parsing each cell as the
+/// type of its field in `schema`.
+pub fn batch_from_rows(
+    schema: &SchemaRef,
+    rows: &[Vec<Option<String>>],
+) -> Result<RecordBatch, Error> {
+    let options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+    schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let text: StringArray = rows
+                .iter()
+                .map(|row| row.get(i).and_then(|v| v.as_deref()))
+                .collect();
+            cast_with_options(&text, field.data_type(), &options)
+        })
+        .collect::<Result<Vec<ArrayRef>, _>>()
+        .and_then(|columns| RecordBatch::try_new(schema.clone(), columns))
+        .map_err(|e| Error::Other(format!("failed to convert rows to Arrow: {}", e)))
+}
+
+/// Map a statement manifest schema to an Arrow schema.
+///
+/// Complex and interval types map to `Utf8`, matching how the SQL Statement
+/// API serializes them in Arrow results.
+pub fn schema(schema: &Schema) -> SchemaRef {
+    let mut columns: Vec<_> = schema.columns.iter().collect();
+    columns.sort_by_key(|c| c.position);
+
+    let fields: Vec<Field> = columns
+        .into_iter()
+        .map(|c| {
+            let data_type = data_type(
+                c.type_name.as_deref().unwrap_or("STRING"),
+                c.type_precision,
+                c.type_scale,
+            );
+            Field::new(&c.name, data_type, true)
+        })
+        .collect();
+    Arc::new(ArrowSchema::new(fields))
+}
+
+fn data_type(type_name: &str, precision: Option<i32>, scale: Option<i32>) -> DataType {
+    match type_name.to_ascii_uppercase().as_str() {
+        "BOOLEAN" => DataType::Boolean,
+        "BYTE" => DataType::Int8,
+        "SHORT" => DataType::Int16,
+        "INT" => DataType::Int32,
+        "LONG" => DataType::Int64,
+        "FLOAT" => DataType::Float32,
+        "DOUBLE" => DataType::Float64,
+        "DATE" => DataType::Date32,
+        "TIMESTAMP" => DataType::Timestamp(TimeUnit::Microsecond, Some("Etc/UTC".into())),
+        "TIMESTAMP_NTZ" => DataType::Timestamp(TimeUnit::Microsecond, None),
+        "BINARY" => DataType::Binary,
+        "DECIMAL" => DataType::Decimal128(precision.unwrap_or(10) as u8, scale.unwrap_or(0) as i8),
+        "NULL" => DataType::Null,
+        _ => DataType::Utf8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Column;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::ipc::writer::StreamWriter;
+
+    fn column(name: &str, type_name: &str, position: i32) -> Column {
+        Column {
+            name: name.into(),
+            type_name: Some(type_name.into()),
+            type_text: None,
+            type_precision: None,
+            type_scale: None,
+            position,
+        }
+    }
+
+    #[test]
+    fn test_schema_from_manifest() {
+        let manifest = Schema {
+            column_count: Some(3),
+            columns: vec![
+                column("tags", "ARRAY", 2),
+                column("id", "INT", 0),
+                column("created", "TIMESTAMP_NTZ", 1),
+            ],
+        };
+
+        let schema = schema(&manifest);
+        let names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names, ["id", "created", "tags"]);
+        assert_eq!(schema.field(0).data_type(), &DataType::Int32);
+        assert_eq!(
+            schema.field(1).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+        assert_eq!(schema.field(2).data_type(), &DataType::Utf8);
+    }
+
+    #[test]
+    fn test_decode_ipc_stream() {
+        let schema = Arc::new(ArrowSchema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec!["a", "b"])),
+            ],
+        )
+        .unwrap();
+
+        let mut data = Vec::new();
+        let mut writer = StreamWriter::try_new(&mut data, &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+        drop(writer);
+
+        let batches = decode(&data).unwrap();
+        assert_eq!(batches, vec![batch]);
+        assert!(decode(b"not arrow").is_err());
+    }
+}
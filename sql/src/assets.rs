@@ -0,0 +1,1069 @@
+use crate::alert_types::{
+    Alert, AlertCondition, AlertLifecycleState, CreateAlertRequest, CreateAlertRequestAlert,
+    UpdateAlertRequest, UpdateAlertRequestAlert,
+};
+use crate::query_types::{
+    CreateQueryRequest, CreateQueryRequestQuery, Query, QueryLifecycleState, QueryParameter,
+    RunAsMode, UpdateQueryRequest, UpdateQueryRequestQuery,
+};
+use crate::types::{CreateVisualizationRequest, UpdateVisualizationRequest, Visualization};
+use crate::{Alerts, Queries, QueryVisualizations};
+use databricks_core::{Client, Error};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Current version of the [`QueryBundle`] file format.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// File format of a [`QueryBundle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BundleFormat {
+    Json,
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl BundleFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            BundleFormat::Json => "json",
+            #[cfg(feature = "yaml")]
+            BundleFormat::Yaml => "yaml",
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, Error> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Ok(BundleFormat::Json),
+            #[cfg(feature = "yaml")]
+            Some("yaml" | "yml") => Ok(BundleFormat::Yaml),
+            _ => Err(Error::Other(format!(
+                "unrecognized bundle file extension: {}",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// A saved query with its visualizations and alerts, in a form that can be
+/// checked into version control.
+///
+/// Server-assigned state (owners, timestamps, alert state) is left out. Ids
+/// are kept so renamed objects are still matched on the next
+/// [`SqlAssets::sync`], but are optional: a bundle without ids is matched by
+/// name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryBundle {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warehouse_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catalog: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_as_mode: Option<RunAsMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apply_auto_limit: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<QueryParameter>,
+    /// Sidecar file holding the SQL, relative to the bundle file. When unset
+    /// the SQL is stored inline in `query_text`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_file: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub query_text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub visualizations: Vec<VisualizationSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alerts: Vec<AlertSpec>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisualizationSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub visualization_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub options: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<AlertCondition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_subject: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify_on_ok: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seconds_to_retrigger: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_path: Option<String>,
+}
+
+impl QueryBundle {
+    fn new(query: Query, visualizations: Vec<Visualization>, alerts: Vec<Alert>) -> Self {
+        Self {
+            version: BUNDLE_VERSION,
+            id: query.id,
+            display_name: query.display_name.unwrap_or_default(),
+            description: query.description,
+            parent_path: query.parent_path,
+            warehouse_id: query.warehouse_id,
+            catalog: query.catalog,
+            schema: query.schema,
+            run_as_mode: query.run_as_mode,
+            apply_auto_limit: query.apply_auto_limit,
+            tags: query.tags.unwrap_or_default(),
+            parameters: query.parameters.unwrap_or_default(),
+            query_file: None,
+            query_text: query.query_text.unwrap_or_default(),
+            visualizations: visualizations
+                .into_iter()
+                .map(VisualizationSpec::from)
+                .collect(),
+            alerts: alerts.into_iter().map(AlertSpec::from).collect(),
+        }
+    }
+
+    /// Serialize the bundle, with the SQL inline.
+    pub fn to_string(&self, format: BundleFormat) -> Result<String, Error> {
+        match format {
+            BundleFormat::Json => Ok(serde_json::to_string_pretty(self)? + "\n"),
+            #[cfg(feature = "yaml")]
+            BundleFormat::Yaml => serde_yaml::to_string(self).map_err(yaml_error),
+        }
+    }
+
+    /// Parse a bundle. A `query_file` reference is not resolved.
+    pub fn parse(s: &str, format: BundleFormat) -> Result<Self, Error> {
+        let bundle: Self = match format {
+            BundleFormat::Json => serde_json::from_str(s)?,
+            #[cfg(feature = "yaml")]
+            BundleFormat::Yaml => serde_yaml::from_str(s).map_err(yaml_error)?,
+        };
+        if bundle.version > BUNDLE_VERSION {
+            return Err(Error::Other(format!(
+                "unsupported bundle version {} (latest is {})",
+                bundle.version, BUNDLE_VERSION
+            )));
+        }
+        Ok(bundle)
+    }
+
+    /// Write `<name>.query.<ext>` and a `<name>.sql` sidecar into `dir`,
+    /// where `<name>` is derived from the display name. Returns the path of
+    /// the bundle file.
+    pub fn write(&self, dir: impl AsRef<Path>, format: BundleFormat) -> Result<PathBuf, Error> {
+        let dir = dir.as_ref();
+        let name = file_stem(&self.display_name);
+        let sql_file = format!("{}.sql", name);
+        let path = dir.join(format!("{}.query.{}", name, format.extension()));
+
+        let mut bundle = self.clone();
+        let mut sql = std::mem::take(&mut bundle.query_text);
+        if !sql.ends_with('\n') {
+            sql.push('\n');
+        }
+        bundle.query_file = Some(sql_file.clone());
+
+        write_file(&dir.join(sql_file), &sql)?;
+        write_file(&path, &bundle.to_string(format)?)?;
+        Ok(path)
+    }
+
+    /// Read a bundle file, loading the SQL from its sidecar file if it has
+    /// one. The format is taken from the file extension.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let mut bundle = Self::parse(&read_file(path)?, BundleFormat::from_path(path)?)?;
+        if let Some(query_file) = &bundle.query_file {
+            let sql_path = path.parent().unwrap_or(Path::new("")).join(query_file);
+            bundle.query_text = read_file(&sql_path)?.trim_end().to_string();
+        }
+        Ok(bundle)
+    }
+
+    fn create_request(&self) -> CreateQueryRequestQuery {
+        CreateQueryRequestQuery {
+            apply_auto_limit: self.apply_auto_limit,
+            catalog: self.catalog.clone(),
+            description: self.description.clone(),
+            display_name: Some(self.display_name.clone()),
+            parameters: non_empty(&self.parameters),
+            parent_path: self.parent_path.clone(),
+            query_text: Some(self.query_text.trim_end().to_string()),
+            run_as_mode: self.run_as_mode,
+            schema: self.schema.clone(),
+            tags: non_empty(&self.tags),
+            warehouse_id: self.warehouse_id.clone(),
+        }
+    }
+
+    fn update_request(&self) -> UpdateQueryRequestQuery {
+        UpdateQueryRequestQuery {
+            apply_auto_limit: self.apply_auto_limit,
+            catalog: self.catalog.clone(),
+            description: self.description.clone(),
+            display_name: Some(self.display_name.clone()),
+            owner_user_name: None,
+            parameters: non_empty(&self.parameters),
+            query_text: Some(self.query_text.trim_end().to_string()),
+            run_as_mode: self.run_as_mode,
+            schema: self.schema.clone(),
+            tags: non_empty(&self.tags),
+            warehouse_id: self.warehouse_id.clone(),
+        }
+    }
+}
+
+impl From<Visualization> for VisualizationSpec {
+    fn from(v: Visualization) -> Self {
+        Self {
+            id: v.id,
+            name: v.name.unwrap_or_default(),
+            visualization_type: v.visualization_type.unwrap_or_default(),
+            description: v.description,
+            options: v.options.unwrap_or(Value::Null),
+        }
+    }
+}
+
+impl VisualizationSpec {
+    fn update_request(&self) -> UpdateVisualizationRequest {
+        UpdateVisualizationRequest {
+            name: Some(self.name.clone()),
+            description: self.description.clone(),
+            options: Some(self.options.clone()),
+            visualization_type: Some(self.visualization_type.clone()),
+        }
+    }
+}
+
+impl From<Alert> for AlertSpec {
+    fn from(a: Alert) -> Self {
+        Self {
+            id: a.id,
+            display_name: a.display_name.unwrap_or_default(),
+            condition: a.condition,
+            custom_subject: a.custom_subject,
+            custom_body: a.custom_body,
+            notify_on_ok: a.notify_on_ok,
+            seconds_to_retrigger: a.seconds_to_retrigger,
+            parent_path: a.parent_path,
+        }
+    }
+}
+
+impl AlertSpec {
+    fn update_request(&self, query_id: &str) -> UpdateAlertRequestAlert {
+        UpdateAlertRequestAlert {
+            condition: self.condition.clone(),
+            custom_body: self.custom_body.clone(),
+            custom_subject: self.custom_subject.clone(),
+            display_name: Some(self.display_name.clone()),
+            notify_on_ok: self.notify_on_ok,
+            owner_user_name: None,
+            query_id: Some(query_id.to_string()),
+            seconds_to_retrigger: self.seconds_to_retrigger,
+        }
+    }
+}
+
+/// Kind of object changed by [`SqlAssets::sync`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    Query,
+    Visualization,
+    Alert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    Created,
+    Updated,
+    Deleted,
+    Unchanged,
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncChange {
+    pub kind: AssetKind,
+    pub id: String,
+    pub name: String,
+    pub action: SyncAction,
+}
+
+/// Outcome of a [`SqlAssets::sync`].
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    pub changes: Vec<SyncChange>,
+}
+
+impl SyncReport {
+    /// Whether anything was created, updated or deleted.
+    pub fn changed(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| c.action != SyncAction::Unchanged)
+    }
+
+    fn push(&mut self, kind: AssetKind, id: &str, name: &str, action: SyncAction) {
+        self.changes.push(SyncChange {
+            kind,
+            id: id.to_string(),
+            name: name.to_string(),
+            action,
+        });
+    }
+}
+
+/// Exports saved queries, with their visualizations and alerts, to
+/// [`QueryBundle`]s and syncs bundles back to the workspace.
+///
+/// ```no_run
+/// # async fn run(client: databricks_core::Client) -> Result<(), databricks_core::Error> {
+/// use databricks_sql::{BundleFormat, QueryBundle, SqlAssets};
+///
+/// let assets = SqlAssets::new(client);
+///
+/// // Export to sql/revenue.query.json and sql/revenue.sql
+/// let path = assets.export("query-id").await?.write("sql", BundleFormat::Json)?;
+///
+/// // ... edit, commit, then apply
+/// let mut bundle = QueryBundle::read(&path)?;
+/// let report = assets.sync(&mut bundle).await?;
+/// if report.changed() {
+///     bundle.write("sql", BundleFormat::Json)?; // record new ids
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct SqlAssets {
+    queries: Queries,
+    visualizations: QueryVisualizations,
+    alerts: Alerts,
+    prune: bool,
+}
+
+impl SqlAssets {
+    pub fn new(client: Client) -> Self {
+        Self {
+            queries: Queries::new(client.clone()),
+            visualizations: QueryVisualizations::new(client.clone()),
+            alerts: Alerts::new(client),
+            prune: true,
+        }
+    }
+
+    /// Whether [`SqlAssets::sync`] deletes visualizations and alerts on the
+    /// query that are not in the bundle (default true).
+    pub fn prune(mut self, prune: bool) -> Self {
+        self.prune = prune;
+        self
+    }
+
+    /// Export a query with its visualizations and alerts.
+    pub async fn export(&self, query_id: &str) -> Result<QueryBundle, Error> {
+        let query = self.queries.get(query_id).await?;
+        let visualizations = self.visualizations.list_all(query_id).collect_all().await?;
+        let alerts = self.query_alerts(query_id).await?;
+        Ok(QueryBundle::new(query, visualizations, alerts))
+    }
+
+    /// Export every query that is not in the trash.
+    ///
+    /// Fails if two queries would be written to the same files by
+    /// [`QueryBundle::write`], e.g. "Sales / EU" and "sales-eu"; rename one
+    /// of them first.
+    pub async fn export_all(&self) -> Result<Vec<QueryBundle>, Error> {
+        let mut queries = self.queries.list_all().collect_all().await?;
+        queries.retain(|q| q.id.is_some() && is_active_query(q));
+
+        let mut stems: HashMap<String, &Query> = HashMap::new();
+        for query in &queries {
+            let name = query.display_name.as_deref().unwrap_or_default();
+            if let Some(other) = stems.insert(file_stem(name), query) {
+                return Err(Error::Other(format!(
+                    "queries {:?} ({}) and {:?} ({}) would be exported to the same file {}",
+                    other.display_name.as_deref().unwrap_or_default(),
+                    other.id.as_deref().unwrap_or_default(),
+                    name,
+                    query.id.as_deref().unwrap_or_default(),
+                    file_stem(name),
+                )));
+            }
+        }
+
+        let alerts = self.active_alerts().await?;
+        let mut bundles = Vec::new();
+        for query in queries {
+            let Some(id) = query.id.clone() else {
+                continue;
+            };
+            let visualizations = self.visualizations.list_all(&id).collect_all().await?;
+            let query_alerts = alerts
+                .iter()
+                .filter(|a| a.query_id.as_deref() == Some(&id))
+                .cloned()
+                .collect();
+            bundles.push(QueryBundle::new(query, visualizations, query_alerts));
+        }
+        Ok(bundles)
+    }
+
+    /// Create or update the query, visualizations and alerts in `bundle` so
+    /// the workspace matches it.
+    ///
+    /// The query is matched by its stored id, falling back to its display
+    /// name (and `parent_path`, if set); visualizations and alerts are
+    /// matched the same way among those attached to the query. Objects that
+    /// already match are left untouched, so syncing twice is a no-op. The ids
+    /// of matched and created objects are written back into `bundle`.
+    pub async fn sync(&self, bundle: &mut QueryBundle) -> Result<SyncReport, Error> {
+        let mut report = SyncReport::default();
+
+        let query_id = match self.find_query(bundle).await? {
+            Some(existing) => {
+                let id = existing.id.clone().unwrap_or_default();
+                let current = QueryBundle::new(existing, Vec::new(), Vec::new());
+                let desired = serde_json::to_value(bundle.update_request())?;
+                let mask =
+                    changed_fields(&serde_json::to_value(current.update_request())?, &desired);
+                if mask.is_empty() {
+                    report.push(
+                        AssetKind::Query,
+                        &id,
+                        &bundle.display_name,
+                        SyncAction::Unchanged,
+                    );
+                } else {
+                    let request = UpdateQueryRequest {
+                        query: Some(bundle.update_request()),
+                        update_mask: mask.join(","),
+                    };
+                    self.queries.update(&id, &request).await?;
+                    report.push(
+                        AssetKind::Query,
+                        &id,
+                        &bundle.display_name,
+                        SyncAction::Updated,
+                    );
+                }
+                id
+            }
+            None => {
+                let request = CreateQueryRequest {
+                    query: Some(bundle.create_request()),
+                };
+                let id = self.queries.create(&request).await?.id.unwrap_or_default();
+                report.push(
+                    AssetKind::Query,
+                    &id,
+                    &bundle.display_name,
+                    SyncAction::Created,
+                );
+                id
+            }
+        };
+        bundle.id = Some(query_id.clone());
+
+        self.sync_visualizations(&query_id, &mut bundle.visualizations, &mut report)
+            .await?;
+        self.sync_alerts(&query_id, &mut bundle.alerts, &mut report)
+            .await?;
+        Ok(report)
+    }
+
+    async fn find_query(&self, bundle: &QueryBundle) -> Result<Option<Query>, Error> {
+        if let Some(id) = &bundle.id {
+            match self.queries.get(id).await {
+                Ok(query) if is_active_query(&query) => return Ok(Some(query)),
+                Ok(_) => {}
+                Err(e) if e.is_not_found() => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let queries = self.queries.list_all().collect_all().await?;
+        Ok(queries.into_iter().find(|q| {
+            is_active_query(q)
+                && q.display_name.as_deref() == Some(&bundle.display_name)
+                && (bundle.parent_path.is_none() || q.parent_path == bundle.parent_path)
+        }))
+    }
+
+    async fn sync_visualizations(
+        &self,
+        query_id: &str,
+        specs: &mut [VisualizationSpec],
+        report: &mut SyncReport,
+    ) -> Result<(), Error> {
+        let mut existing = self.visualizations.list_all(query_id).collect_all().await?;
+
+        for spec in specs.iter_mut() {
+            let position = match_position(&existing, spec.id.as_deref(), &spec.name, |v| {
+                (v.id.as_deref(), v.name.as_deref())
+            });
+            let name = spec.name.clone();
+            let id = match position.map(|i| existing.remove(i)) {
+                Some(current) => {
+                    let id = current.id.clone().unwrap_or_default();
+                    let current = VisualizationSpec::from(current);
+                    let request = spec.update_request();
+                    if serde_json::to_value(current.update_request())?
+                        == serde_json::to_value(&request)?
+                    {
+                        report.push(AssetKind::Visualization, &id, &name, SyncAction::Unchanged);
+                    } else {
+                        self.visualizations.update(&id, &request).await?;
+                        report.push(AssetKind::Visualization, &id, &name, SyncAction::Updated);
+                    }
+                    id
+                }
+                None => {
+                    let request = CreateVisualizationRequest {
+                        query_id: query_id.to_string(),
+                        visualization_type: spec.visualization_type.clone(),
+                        name: spec.name.clone(),
+                        description: spec.description.clone(),
+                        options: spec.options.clone(),
+                    };
+                    let id = self
+                        .visualizations
+                        .create(&request)
+                        .await?
+                        .id
+                        .unwrap_or_default();
+                    report.push(AssetKind::Visualization, &id, &name, SyncAction::Created);
+                    id
+                }
+            };
+            spec.id = Some(id);
+        }
+
+        if self.prune {
+            for extra in existing {
+                let id = extra.id.unwrap_or_default();
+                self.visualizations.delete(&id).await?;
+                let name = extra.name.unwrap_or_default();
+                report.push(AssetKind::Visualization, &id, &name, SyncAction::Deleted);
+            }
+        }
+        Ok(())
+    }
+
+    async fn sync_alerts(
+        &self,
+        query_id: &str,
+        specs: &mut [AlertSpec],
+        report: &mut SyncReport,
+    ) -> Result<(), Error> {
+        let mut existing = self.query_alerts(query_id).await?;
+
+        for spec in specs.iter_mut() {
+            let position = match_position(&existing, spec.id.as_deref(), &spec.display_name, |a| {
+                (a.id.as_deref(), a.display_name.as_deref())
+            });
+            let name = spec.display_name.clone();
+            let id = match position.map(|i| existing.remove(i)) {
+                Some(current) => {
+                    let id = current.id.clone().unwrap_or_default();
+                    let current = AlertSpec::from(current);
+                    let desired = serde_json::to_value(spec.update_request(query_id))?;
+                    let mask = changed_fields(
+                        &serde_json::to_value(current.update_request(query_id))?,
+                        &desired,
+                    );
+                    if mask.is_empty() {
+                        report.push(AssetKind::Alert, &id, &name, SyncAction::Unchanged);
+                    } else {
+                        let request = UpdateAlertRequest {
+                            alert: Some(spec.update_request(query_id)),
+                            update_mask: mask.join(","),
+                        };
+                        self.alerts.update(&id, &request).await?;
+                        report.push(AssetKind::Alert, &id, &name, SyncAction::Updated);
+                    }
+                    id
+                }
+                None => {
+                    let request = CreateAlertRequest {
+                        alert: Some(CreateAlertRequestAlert {
+                            condition: spec.condition.clone(),
+                            custom_body: spec.custom_body.clone(),
+                            custom_subject: spec.custom_subject.clone(),
+                            display_name: Some(spec.display_name.clone()),
+                            notify_on_ok: spec.notify_on_ok,
+                            parent_path: spec.parent_path.clone(),
+                            query_id: Some(query_id.to_string()),
+                            seconds_to_retrigger: spec.seconds_to_retrigger,
+                        }),
+                    };
+                    let id = self.alerts.create(&request).await?.id.unwrap_or_default();
+                    report.push(AssetKind::Alert, &id, &name, SyncAction::Created);
+                    id
+                }
+            };
+            spec.id = Some(id);
+        }
+
+        if self.prune {
+            for extra in existing {
+                let id = extra.id.unwrap_or_default();
+                self.alerts.delete(&id).await?;
+                let name = extra.display_name.unwrap_or_default();
+                report.push(AssetKind::Alert, &id, &name, SyncAction::Deleted);
+            }
+        }
+        Ok(())
+    }
+
+    async fn query_alerts(&self, query_id: &str) -> Result<Vec<Alert>, Error> {
+        let mut alerts = self.active_alerts().await?;
+        alerts.retain(|a| a.query_id.as_deref() == Some(query_id));
+        Ok(alerts)
+    }
+
+    async fn active_alerts(&self) -> Result<Vec<Alert>, Error> {
+        let mut alerts = self.alerts.list_all().collect_all().await?;
+        alerts.retain(|a| a.lifecycle_state != Some(AlertLifecycleState::Trashed));
+        Ok(alerts)
+    }
+}
+
+fn is_active_query(query: &Query) -> bool {
+    query.lifecycle_state != Some(QueryLifecycleState::Trashed)
+}
+
+/// Index of the object in `existing` with id `id`, or else the first one
+/// named `name`.
+fn match_position<T>(
+    existing: &[T],
+    id: Option<&str>,
+    name: &str,
+    key: impl Fn(&T) -> (Option<&str>, Option<&str>),
+) -> Option<usize> {
+    id.and_then(|id| existing.iter().position(|e| key(e).0 == Some(id)))
+        .or_else(|| existing.iter().position(|e| key(e).1 == Some(name)))
+}
+
+/// Top-level fields that differ between two serialized update requests, for
+/// use as an `update_mask`.
+fn changed_fields(current: &Value, desired: &Value) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let current = current.as_object().unwrap_or(&empty);
+    let desired = desired.as_object().unwrap_or(&empty);
+
+    let mut fields: Vec<String> = current
+        .keys()
+        .chain(desired.keys())
+        .filter(|k| current.get(*k) != desired.get(*k))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields.dedup();
+    fields
+}
+
+fn non_empty<T: Clone>(items: &[T]) -> Option<Vec<T>> {
+    (!items.is_empty()).then(|| items.to_vec())
+}
+
+/// File name stem for a display name: lowercase ASCII letters and digits,
+/// with runs of anything else replaced by `-`.
+fn file_stem(display_name: &str) -> String {
+    let mut stem = String::with_capacity(display_name.len());
+    for c in display_name.chars() {
+        if c.is_ascii_alphanumeric() {
+            stem.push(c.to_ascii_lowercase());
+        } else if !stem.is_empty() && !stem.ends_with('-') {
+            stem.push('-');
+        }
+    }
+    let stem = stem.trim_end_matches('-');
+    if stem.is_empty() {
+        "query".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path)
+        .map_err(|e| Error::Other(format!("failed to read {}: {}", path.display(), e)))
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), Error> {
+    std::fs::write(path, contents)
+        .map_err(|e| Error::Other(format!("failed to write {}: {}", path.display(), e)))
+}
+
+#[cfg(feature = "yaml")]
+fn yaml_error(e: serde_yaml::Error) -> Error {
+    Error::Other(format!("invalid YAML bundle: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use databricks_core::transport::mock::MockTransport;
+    use serde_json::json;
+
+    const QUERIES: &str = "/api/2.0/sql/queries";
+    const ALERTS: &str = "/api/2.0/sql/alerts";
+
+    fn assets(mock: &MockTransport) -> SqlAssets {
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        SqlAssets::new(client)
+    }
+
+    fn bundle(value: Value) -> QueryBundle {
+        serde_json::from_value(value).unwrap()
+    }
+
+    /// Method and path of every request other than a `GET`.
+    fn writes(mock: &MockTransport) -> Vec<(String, String)> {
+        mock.requests()
+            .into_iter()
+            .filter(|r| r.method != "GET")
+            .map(|r| (r.method, r.path))
+            .collect()
+    }
+
+    fn actions(report: &SyncReport) -> Vec<(AssetKind, &str, SyncAction)> {
+        report
+            .changes
+            .iter()
+            .map(|c| (c.kind, c.id.as_str(), c.action))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_sync_creates_new_query() {
+        let mock = MockTransport::new()
+            .on("GET", QUERIES, 200, json!({"results": []}))
+            .on(
+                "POST",
+                QUERIES,
+                200,
+                json!({"id": "q1", "display_name": "Revenue"}),
+            )
+            .on(
+                "GET",
+                "/api/2.0/sql/queries/q1/visualizations",
+                200,
+                json!({"results": []}),
+            )
+            .on("GET", ALERTS, 200, json!({"results": []}));
+        let mut bundle = bundle(json!({
+            "version": 1,
+            "display_name": "Revenue",
+            "query_text": "SELECT 1",
+        }));
+
+        let report = assets(&mock).sync(&mut bundle).await.unwrap();
+        assert_eq!(
+            actions(&report),
+            [(AssetKind::Query, "q1", SyncAction::Created)]
+        );
+        assert_eq!(bundle.id.as_deref(), Some("q1"));
+        assert_eq!(writes(&mock), [("POST".into(), QUERIES.into())]);
+        let created = mock
+            .requests()
+            .into_iter()
+            .find(|r| r.method == "POST")
+            .unwrap();
+        let body = created.body.unwrap();
+        assert_eq!(body["query"]["query_text"], "SELECT 1");
+    }
+
+    #[tokio::test]
+    async fn test_sync_updates_query_matched_by_id() {
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                "/api/2.0/sql/queries/q1",
+                200,
+                json!({"id": "q1", "display_name": "Old name", "query_text": "SELECT 1"}),
+            )
+            .on("PATCH", "/api/2.0/sql/queries/q1", 200, json!({"id": "q1"}))
+            .on(
+                "GET",
+                "/api/2.0/sql/queries/q1/visualizations",
+                200,
+                json!({"results": []}),
+            )
+            .on("GET", ALERTS, 200, json!({"results": []}));
+        let mut bundle = bundle(json!({
+            "version": 1,
+            "id": "q1",
+            "display_name": "Revenue",
+            "query_text": "SELECT 2",
+        }));
+
+        let report = assets(&mock).sync(&mut bundle).await.unwrap();
+        assert_eq!(
+            actions(&report),
+            [(AssetKind::Query, "q1", SyncAction::Updated)]
+        );
+        assert_eq!(
+            writes(&mock),
+            [("PATCH".into(), "/api/2.0/sql/queries/q1".into())]
+        );
+        let update = mock
+            .requests()
+            .into_iter()
+            .find(|r| r.method == "PATCH")
+            .unwrap();
+        assert_eq!(
+            update.body.unwrap()["update_mask"],
+            "display_name,query_text"
+        );
+        // Matched by id, so the query list was never fetched.
+        assert!(!mock
+            .requests()
+            .iter()
+            .any(|r| r.path.split('?').next() == Some(QUERIES)));
+    }
+
+    #[tokio::test]
+    async fn test_sync_updates_query_matched_by_name() {
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                QUERIES,
+                200,
+                json!({"results": [
+                    {"id": "q7", "display_name": "Other", "query_text": "SELECT 2"},
+                    {"id": "q1", "display_name": "Revenue", "query_text": "SELECT 1"},
+                ]}),
+            )
+            .on("PATCH", "/api/2.0/sql/queries/q1", 200, json!({"id": "q1"}))
+            .on(
+                "GET",
+                "/api/2.0/sql/queries/q1/visualizations",
+                200,
+                json!({"results": []}),
+            )
+            .on("GET", ALERTS, 200, json!({"results": []}));
+        let mut bundle = bundle(json!({
+            "version": 1,
+            "display_name": "Revenue",
+            "query_text": "SELECT 2",
+        }));
+
+        let report = assets(&mock).sync(&mut bundle).await.unwrap();
+        assert_eq!(
+            actions(&report),
+            [(AssetKind::Query, "q1", SyncAction::Updated)]
+        );
+        assert_eq!(bundle.id.as_deref(), Some("q1"));
+        let update = mock
+            .requests()
+            .into_iter()
+            .find(|r| r.method == "PATCH")
+            .unwrap();
+        assert_eq!(update.body.unwrap()["update_mask"], "query_text");
+    }
+
+    #[tokio::test]
+    async fn test_sync_prunes_visualizations_and_alerts_not_in_bundle() {
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                "/api/2.0/sql/queries/q1",
+                200,
+                json!({"id": "q1", "display_name": "Revenue", "query_text": "SELECT 1"}),
+            )
+            .on(
+                "GET",
+                "/api/2.0/sql/queries/q1/visualizations",
+                200,
+                json!({"results": [{"id": "v1", "name": "Old chart", "type": "CHART"}]}),
+            )
+            .on(
+                "GET",
+                ALERTS,
+                200,
+                json!({"results": [
+                    {"id": "a1", "display_name": "Old alert", "query_id": "q1"},
+                    {"id": "a2", "display_name": "Elsewhere", "query_id": "q2"},
+                ]}),
+            )
+            .on("DELETE", "/api/2.0/sql/visualizations/v1", 200, json!({}))
+            .on("DELETE", "/api/2.0/sql/alerts/a1", 200, json!({}));
+        let mut bundle = bundle(json!({
+            "version": 1,
+            "id": "q1",
+            "display_name": "Revenue",
+            "query_text": "SELECT 1",
+        }));
+
+        let report = assets(&mock).sync(&mut bundle).await.unwrap();
+        assert_eq!(
+            actions(&report),
+            [
+                (AssetKind::Query, "q1", SyncAction::Unchanged),
+                (AssetKind::Visualization, "v1", SyncAction::Deleted),
+                (AssetKind::Alert, "a1", SyncAction::Deleted),
+            ]
+        );
+        assert_eq!(
+            writes(&mock),
+            [
+                ("DELETE".into(), "/api/2.0/sql/visualizations/v1".into()),
+                ("DELETE".into(), "/api/2.0/sql/alerts/a1".into()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repeated_sync_issues_no_writes() {
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                QUERIES,
+                200,
+                json!({"results": [
+                    {"id": "q1", "display_name": "Revenue", "query_text": "SELECT 1\n"},
+                ]}),
+            )
+            .on(
+                "GET",
+                "/api/2.0/sql/queries/q1",
+                200,
+                json!({"id": "q1", "display_name": "Revenue", "query_text": "SELECT 1\n"}),
+            )
+            .on(
+                "GET",
+                "/api/2.0/sql/queries/q1/visualizations",
+                200,
+                json!({"results": [
+                    {"id": "v1", "name": "Chart", "type": "CHART", "options": {"x": "day"}},
+                ]}),
+            )
+            .on(
+                "GET",
+                ALERTS,
+                200,
+                json!({"results": [{
+                    "id": "a1",
+                    "display_name": "High",
+                    "query_id": "q1",
+                    "seconds_to_retrigger": 60,
+                }]}),
+            );
+        // No ids: the first sync matches everything by name.
+        let mut bundle = bundle(json!({
+            "version": 1,
+            "display_name": "Revenue",
+            "query_text": "SELECT 1",
+            "visualizations": [{"name": "Chart", "type": "CHART", "options": {"x": "day"}}],
+            "alerts": [{"display_name": "High", "seconds_to_retrigger": 60}],
+        }));
+
+        let assets = assets(&mock);
+        for _ in 0..2 {
+            let report = assets.sync(&mut bundle).await.unwrap();
+            assert!(!report.changed());
+            assert_eq!(
+                actions(&report),
+                [
+                    (AssetKind::Query, "q1", SyncAction::Unchanged),
+                    (AssetKind::Visualization, "v1", SyncAction::Unchanged),
+                    (AssetKind::Alert, "a1", SyncAction::Unchanged),
+                ]
+            );
+        }
+        assert_eq!(bundle.id.as_deref(), Some("q1"));
+        assert_eq!(bundle.visualizations[0].id.as_deref(), Some("v1"));
+        assert_eq!(bundle.alerts[0].id.as_deref(), Some("a1"));
+        assert!(writes(&mock).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_all_rejects_colliding_file_names() {
+        let mock = MockTransport::new().on(
+            "GET",
+            QUERIES,
+            200,
+            json!({"results": [
+                {"id": "q1", "display_name": "Sales / EU"},
+                {"id": "q2", "display_name": "sales-eu"},
+            ]}),
+        );
+
+        let error = assets(&mock).export_all().await.unwrap_err();
+        assert!(error.to_string().contains("same file sales-eu"));
+    }
+
+    #[test]
+    fn test_changed_fields() {
+        let current = serde_json::json!({"display_name": "a", "description": "old", "tags": ["x"]});
+        let desired =
+            serde_json::json!({"display_name": "a", "query_text": "SELECT 1", "tags": ["x"]});
+        assert_eq!(
+            changed_fields(&current, &desired),
+            ["description", "query_text"]
+        );
+        assert!(changed_fields(&desired, &desired).is_empty());
+    }
+
+    #[test]
+    fn test_bundle_round_trip_with_sidecar() {
+        let dir = std::env::temp_dir().join(format!("sql-assets-{:016x}", rand::random::<u64>()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let bundle: QueryBundle = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "id": "q1",
+            "display_name": "Daily Revenue (EU)",
+            "query_text": "SELECT sum(amount)\nFROM sales",
+            "visualizations": [{"name": "Chart", "type": "CHART", "options": {"x": "day"}}],
+        }))
+        .unwrap();
+
+        let path = bundle.write(&dir, BundleFormat::Json).unwrap();
+        assert!(path.ends_with("daily-revenue-eu.query.json"));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("daily-revenue-eu.sql")).unwrap(),
+            "SELECT sum(amount)\nFROM sales\n"
+        );
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("SELECT"));
+
+        let read = QueryBundle::read(&path).unwrap();
+        assert_eq!(read.query_text, bundle.query_text);
+        assert_eq!(read.id.as_deref(), Some("q1"));
+        assert_eq!(read.visualizations[0].options["x"], "day");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
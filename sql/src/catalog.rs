@@ -1,14 +1,25 @@
-use crate::types::{Request, Response};
+use crate::parameters::{quote_identifier, quote_name};
+use crate::rows::FromRow;
+use crate::types::Request;
 use crate::Statements;
 use databricks_core::Error;
+use futures::TryStreamExt;
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 const POLL_INTERVAL: Duration = Duration::from_secs(1);
 const TIMEOUT: Duration = Duration::from_secs(30);
 
+/// Unity Catalog introspection through a SQL warehouse.
+///
+/// Backed by `information_schema`, so results only include objects the
+/// caller has privileges on.
 pub struct Catalog {
     statements: Statements,
     warehouse_id: String,
+    poll_interval: Duration,
+    timeout: Duration,
 }
 
 impl Catalog {
@@ -16,109 +27,391 @@ impl Catalog {
         Self {
             statements,
             warehouse_id: warehouse_id.into(),
+            poll_interval: POLL_INTERVAL,
+            timeout: TIMEOUT,
         }
     }
 
-    async fn query(&self, sql: &str) -> Result<Response, Error> {
-        let request = Request::new(sql, &self.warehouse_id);
-        self.statements
-            .execute_wait(&request, POLL_INTERVAL, TIMEOUT)
-            .await
+    /// Maximum time to wait for each query (default 30s).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
     }
 
-    fn extract_first_column(response: &Response) -> Vec<String> {
-        response
-            .result
-            .as_ref()
-            .map(|r| {
-                r.data_array
-                    .iter()
-                    .filter_map(|row| row.first().and_then(|v| v.clone()))
-                    .collect()
-            })
-            .unwrap_or_default()
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
     }
 
-    pub async fn list_catalogs(&self) -> Result<Vec<String>, Error> {
-        let response = self.query("SHOW CATALOGS").await?;
-        Ok(Self::extract_first_column(&response))
+    async fn query<T: FromRow>(&self, request: Request) -> Result<Vec<T>, Error> {
+        let response = self
+            .statements
+            .execute_wait(&request, self.poll_interval, self.timeout)
+            .await?;
+        self.statements.rows(&response).try_collect().await
     }
 
-    pub async fn list_schemas(&self, catalog: &str) -> Result<Vec<String>, Error> {
-        let response = self.query(&format!("SHOW SCHEMAS IN {}", catalog)).await?;
-        Ok(Self::extract_first_column(&response))
+    fn request(&self, sql: impl Into<String>) -> Request {
+        Request::new(sql, &self.warehouse_id)
     }
 
-    pub async fn list_tables(&self, catalog: &str, schema: &str) -> Result<Vec<Table>, Error> {
-        let response = self
-            .query(&format!("SHOW TABLES IN {}.{}", catalog, schema))
+    pub async fn list_catalogs(&self) -> Result<Vec<String>, Error> {
+        let rows: Vec<(String,)> = self
+            .query(self.request(
+                "SELECT catalog_name FROM system.information_schema.catalogs \
+                 ORDER BY catalog_name",
+            ))
             .await?;
+        Ok(rows.into_iter().map(|(name,)| name).collect())
+    }
 
-        let tables = response
-            .result
-            .as_ref()
-            .map(|r| {
-                r.data_array
-                    .iter()
-                    .filter_map(|row| {
-                        let schema = row.first().and_then(|v| v.clone())?;
-                        let name = row.get(1).and_then(|v| v.clone())?;
-                        Some(Table { schema, name })
-                    })
-                    .collect()
-            })
-            .unwrap_or_default();
+    pub async fn list_schemas(&self, catalog: &str) -> Result<Vec<String>, Error> {
+        let rows: Vec<(String,)> = self
+            .query(self.request(format!(
+                "SELECT schema_name FROM {}.information_schema.schemata \
+                 ORDER BY schema_name",
+                quote_identifier(catalog)
+            )))
+            .await?;
+        Ok(rows.into_iter().map(|(name,)| name).collect())
+    }
 
-        Ok(tables)
+    pub async fn list_tables(&self, catalog: &str, schema: &str) -> Result<Vec<Table>, Error> {
+        let request = self
+            .request(format!(
+                "SELECT {} FROM {}.information_schema.tables \
+                 WHERE table_schema = :schema ORDER BY table_name",
+                TABLE_COLUMNS,
+                quote_identifier(catalog)
+            ))
+            .bind("schema", schema);
+        self.query(request).await
     }
 
+    /// Columns of a table in ordinal order.
     pub async fn describe_table(
         &self,
         catalog: &str,
         schema: &str,
         table: &str,
     ) -> Result<Vec<Column>, Error> {
-        let response = self
-            .query(&format!("DESCRIBE {}.{}.{}", catalog, schema, table))
-            .await?;
+        let request = self
+            .request(format!(
+                "SELECT column_name AS name, ordinal_position AS position, \
+                 full_data_type AS data_type, is_nullable = 'YES' AS nullable, \
+                 column_default AS default_value, comment, partition_index \
+                 FROM {}.information_schema.columns \
+                 WHERE table_schema = :schema AND table_name = :table \
+                 ORDER BY ordinal_position",
+                quote_identifier(catalog)
+            ))
+            .bind("schema", schema)
+            .bind("table", table);
+        self.query(request).await
+    }
+
+    /// Everything known about a table: columns, partitioning, keys,
+    /// properties and, for views, the view definition.
+    pub async fn table_details(
+        &self,
+        catalog: &str,
+        schema: &str,
+        table: &str,
+    ) -> Result<TableDetails, Error> {
+        let info = self.table(catalog, schema, table);
+        let columns = self.describe_table(catalog, schema, table);
+        let keys = self.keys(catalog, schema, table);
+        let properties = self.properties(catalog, schema, table);
+        let (info, columns, keys, properties) =
+            futures::try_join!(info, columns, keys, properties)?;
+
+        let view_definition = match info.table_type.as_deref() {
+            Some("VIEW") | Some("MATERIALIZED_VIEW") => {
+                self.view_definition(catalog, schema, table).await?
+            }
+            _ => None,
+        };
+
+        let mut partition_columns: Vec<&Column> = columns
+            .iter()
+            .filter(|c| c.partition_index.is_some())
+            .collect();
+        partition_columns.sort_by_key(|c| c.partition_index);
+        let partition_columns = partition_columns
+            .into_iter()
+            .map(|c| c.name.clone())
+            .collect();
+
+        let (primary_key, foreign_keys) = group_keys(keys);
 
-        let columns = response
-            .result
-            .as_ref()
-            .map(|r| {
-                r.data_array
-                    .iter()
-                    .filter_map(|row| {
-                        let name = row.first().and_then(|v| v.clone())?;
-                        let data_type = row.get(1).and_then(|v| v.clone())?;
-                        let nullable = row
-                            .get(2)
-                            .and_then(|v| v.as_deref())
-                            .map(|s| s == "NULL")
-                            .unwrap_or(true);
-                        Some(Column {
-                            name,
-                            data_type,
-                            nullable,
-                        })
-                    })
-                    .collect()
+        Ok(TableDetails {
+            table: info,
+            columns,
+            partition_columns,
+            primary_key,
+            foreign_keys,
+            properties,
+            view_definition,
+        })
+    }
+
+    /// Find tables and views in every catalog whose name matches a SQL `LIKE`
+    /// pattern, case-insensitively (e.g. `%orders%`).
+    pub async fn search(&self, pattern: &str) -> Result<Vec<Table>, Error> {
+        let request = self
+            .request(format!(
+                "SELECT {} FROM system.information_schema.tables \
+                 WHERE table_name ILIKE :pattern AND table_schema <> 'information_schema' \
+                 ORDER BY table_catalog, table_schema, table_name",
+                TABLE_COLUMNS
+            ))
+            .bind("pattern", pattern);
+        self.query(request).await
+    }
+
+    async fn table(&self, catalog: &str, schema: &str, table: &str) -> Result<Table, Error> {
+        let request = self
+            .request(format!(
+                "SELECT {} FROM {}.information_schema.tables \
+                 WHERE table_schema = :schema AND table_name = :table",
+                TABLE_COLUMNS,
+                quote_identifier(catalog)
+            ))
+            .bind("schema", schema)
+            .bind("table", table);
+        self.query(request)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "table {} not found",
+                    quote_name(&[catalog, schema, table])
+                ))
             })
-            .unwrap_or_default();
+    }
 
-        Ok(columns)
+    async fn keys(
+        &self,
+        catalog: &str,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<KeyColumn>, Error> {
+        let catalog = quote_identifier(catalog);
+        let request = self
+            .request(format!(
+                "SELECT tc.constraint_name, tc.constraint_type, kcu.column_name, \
+                 uk.table_catalog AS referenced_catalog, uk.table_schema AS referenced_schema, \
+                 uk.table_name AS referenced_table, uk.column_name AS referenced_column \
+                 FROM {catalog}.information_schema.table_constraints tc \
+                 JOIN {catalog}.information_schema.key_column_usage kcu \
+                   ON kcu.constraint_schema = tc.constraint_schema \
+                  AND kcu.constraint_name = tc.constraint_name \
+                 LEFT JOIN {catalog}.information_schema.referential_constraints rc \
+                   ON rc.constraint_schema = tc.constraint_schema \
+                  AND rc.constraint_name = tc.constraint_name \
+                 LEFT JOIN system.information_schema.key_column_usage uk \
+                   ON uk.constraint_catalog = rc.unique_constraint_catalog \
+                  AND uk.constraint_schema = rc.unique_constraint_schema \
+                  AND uk.constraint_name = rc.unique_constraint_name \
+                  AND uk.ordinal_position = kcu.position_in_unique_constraint \
+                 WHERE tc.table_schema = :schema AND tc.table_name = :table \
+                   AND tc.constraint_type IN ('PRIMARY KEY', 'FOREIGN KEY') \
+                 ORDER BY tc.constraint_name, kcu.ordinal_position"
+            ))
+            .bind("schema", schema)
+            .bind("table", table);
+        self.query(request).await
+    }
+
+    async fn properties(
+        &self,
+        catalog: &str,
+        schema: &str,
+        table: &str,
+    ) -> Result<BTreeMap<String, String>, Error> {
+        let rows: Vec<(String, Option<String>)> = self
+            .query(self.request(format!(
+                "SHOW TBLPROPERTIES {}",
+                quote_name(&[catalog, schema, table])
+            )))
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(key, value)| (key, value.unwrap_or_default()))
+            .collect())
+    }
+
+    async fn view_definition(
+        &self,
+        catalog: &str,
+        schema: &str,
+        table: &str,
+    ) -> Result<Option<String>, Error> {
+        let request = self
+            .request(format!(
+                "SELECT view_definition FROM {}.information_schema.views \
+                 WHERE table_schema = :schema AND table_name = :table",
+                quote_identifier(catalog)
+            ))
+            .bind("schema", schema)
+            .bind("table", table);
+        let rows: Vec<(Option<String>,)> = self.query(request).await?;
+        Ok(rows.into_iter().next().and_then(|(definition,)| definition))
     }
 }
 
-#[derive(Debug, Clone)]
+const TABLE_COLUMNS: &str = "table_catalog AS catalog, table_schema AS `schema`, \
+     table_name AS name, table_type, comment, data_source_format, table_owner AS owner";
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct Table {
+    pub catalog: String,
     pub schema: String,
     pub name: String,
+    /// `MANAGED`, `EXTERNAL`, `VIEW`, `MATERIALIZED_VIEW`, ...
+    pub table_type: Option<String>,
+    pub comment: Option<String>,
+    /// `DELTA`, `PARQUET`, `CSV`, ...
+    pub data_source_format: Option<String>,
+    pub owner: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Column {
     pub name: String,
+    /// 0-based position in the table, as reported by
+    /// `information_schema.columns.ordinal_position`.
+    pub position: i32,
+    /// Full type, e.g. `decimal(10,2)` or `array<string>`.
     pub data_type: String,
     pub nullable: bool,
+    pub default_value: Option<String>,
+    pub comment: Option<String>,
+    /// Position among the partition columns, if the table is partitioned by
+    /// this column.
+    pub partition_index: Option<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TableDetails {
+    pub table: Table,
+    pub columns: Vec<Column>,
+    /// Partition column names in partitioning order.
+    pub partition_columns: Vec<String>,
+    pub primary_key: Option<PrimaryKey>,
+    pub foreign_keys: Vec<ForeignKey>,
+    pub properties: BTreeMap<String, String>,
+    /// SQL text of the view, for views and materialized views.
+    pub view_definition: Option<String>,
+}
+
+/// An informational primary key constraint.
+#[derive(Debug, Clone)]
+pub struct PrimaryKey {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+/// An informational foreign key constraint.
+#[derive(Debug, Clone)]
+pub struct ForeignKey {
+    pub name: String,
+    pub columns: Vec<String>,
+    /// `[catalog, schema, table]` of the referenced table.
+    pub referenced_table: Vec<String>,
+    pub referenced_columns: Vec<String>,
+}
+
+/// One column of a key constraint, as returned by the constraints query.
+#[derive(Deserialize)]
+struct KeyColumn {
+    constraint_name: String,
+    constraint_type: String,
+    column_name: String,
+    referenced_catalog: Option<String>,
+    referenced_schema: Option<String>,
+    referenced_table: Option<String>,
+    referenced_column: Option<String>,
+}
+
+/// Group key columns, ordered by constraint and position, into constraints.
+fn group_keys(keys: Vec<KeyColumn>) -> (Option<PrimaryKey>, Vec<ForeignKey>) {
+    let mut primary_key: Option<PrimaryKey> = None;
+    let mut foreign_keys: Vec<ForeignKey> = Vec::new();
+
+    for key in keys {
+        if key.constraint_type == "PRIMARY KEY" {
+            primary_key
+                .get_or_insert_with(|| PrimaryKey {
+                    name: key.constraint_name.clone(),
+                    columns: Vec::new(),
+                })
+                .columns
+                .push(key.column_name);
+            continue;
+        }
+
+        let foreign_key = match foreign_keys.last_mut() {
+            Some(fk) if fk.name == key.constraint_name => fk,
+            _ => {
+                foreign_keys.push(ForeignKey {
+                    name: key.constraint_name.clone(),
+                    columns: Vec::new(),
+                    referenced_table: [
+                        key.referenced_catalog,
+                        key.referenced_schema,
+                        key.referenced_table,
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+                    referenced_columns: Vec::new(),
+                });
+                foreign_keys.last_mut().expect("just pushed")
+            }
+        };
+        foreign_key.columns.push(key.column_name);
+        foreign_key.referenced_columns.extend(key.referenced_column);
+    }
+
+    (primary_key, foreign_keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str, kind: &str, column: &str, referenced: Option<&str>) -> KeyColumn {
+        KeyColumn {
+            constraint_name: name.into(),
+            constraint_type: kind.into(),
+            column_name: column.into(),
+            referenced_catalog: referenced.map(|_| "main".into()),
+            referenced_schema: referenced.map(|_| "sales".into()),
+            referenced_table: referenced.map(|_| "customers".into()),
+            referenced_column: referenced.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn test_group_keys() {
+        let (primary_key, foreign_keys) = group_keys(vec![
+            key("fk_customer", "FOREIGN KEY", "region", Some("region")),
+            key("fk_customer", "FOREIGN KEY", "customer_id", Some("id")),
+            key("pk_orders", "PRIMARY KEY", "order_id", None),
+        ]);
+
+        let primary_key = primary_key.unwrap();
+        assert_eq!(primary_key.name, "pk_orders");
+        assert_eq!(primary_key.columns, ["order_id"]);
+
+        assert_eq!(foreign_keys.len(), 1);
+        assert_eq!(foreign_keys[0].columns, ["region", "customer_id"]);
+        assert_eq!(
+            foreign_keys[0].referenced_table,
+            ["main", "sales", "customers"]
+        );
+        assert_eq!(foreign_keys[0].referenced_columns, ["region", "id"]);
+    }
 }
@@ -0,0 +1,299 @@
+use crate::result_stream::ResultStream;
+use crate::rows::Row;
+use crate::types::{Column, Response};
+use databricks_core::{Client, Error};
+use futures::io::{AsyncWrite, AsyncWriteExt};
+use futures::TryStreamExt;
+
+/// Bytes buffered before each write to the destination.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// File format for [`Exporter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ExportFormat {
+    /// RFC 4180 CSV with a header row. `NULL` is written as an empty field
+    /// and the empty string as `""`.
+    Csv,
+    /// One JSON object per line, keyed by column name.
+    JsonLines,
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+/// Writes the complete result of a statement to a file or other sink.
+///
+/// Results are streamed chunk by chunk, for any disposition, so they never
+/// have to fit in memory. Destinations implement `futures::io::AsyncWrite`;
+/// wrap tokio writers with `tokio_util::compat`.
+///
+/// ```no_run
+/// # async fn run(client: databricks_core::Client, response: databricks_sql::Response, file: &mut (impl futures::io::AsyncWrite + Unpin)) -> Result<(), databricks_core::Error> {
+/// use databricks_sql::{ExportFormat, Exporter};
+///
+/// let rows = Exporter::new(client)
+///     .export(&response, ExportFormat::Csv, file)
+///     .await?;
+/// println!("exported {} rows", rows);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Exporter {
+    client: Client,
+    prefetch: usize,
+}
+
+impl Exporter {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            prefetch: 2,
+        }
+    }
+
+    /// Number of chunks fetched ahead of the writer (default 2).
+    pub fn prefetch(mut self, chunks: usize) -> Self {
+        self.prefetch = chunks;
+        self
+    }
+
+    /// Write every row of a succeeded statement, returning the row count.
+    pub async fn export<W: AsyncWrite + Unpin>(
+        &self,
+        response: &Response,
+        format: ExportFormat,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        match format {
+            ExportFormat::Csv => self.csv(response, writer).await,
+            ExportFormat::JsonLines => self.json_lines(response, writer).await,
+            #[cfg(feature = "parquet")]
+            ExportFormat::Parquet => self.parquet(response, writer).await,
+        }
+    }
+
+    pub async fn csv<W: AsyncWrite + Unpin>(
+        &self,
+        response: &Response,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        let mut rows = self.rows(response);
+        let mut out = Vec::with_capacity(BUFFER_SIZE);
+        let header = rows.columns().iter().map(|c| Some(c.name.as_str()));
+        write_csv_record(&mut out, header);
+
+        let mut count = 0;
+        while let Some(row) = rows.try_next().await? {
+            write_csv_record(&mut out, row.iter().map(|v| v.as_deref()));
+            count += 1;
+            if out.len() >= BUFFER_SIZE {
+                write(writer, &mut out).await?;
+            }
+        }
+        write(writer, &mut out).await?;
+        writer.flush().await.map_err(write_error)?;
+        Ok(count)
+    }
+
+    pub async fn json_lines<W: AsyncWrite + Unpin>(
+        &self,
+        response: &Response,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        let mut rows = self.rows(response);
+        let columns = rows.columns().to_vec();
+        let mut out = Vec::with_capacity(BUFFER_SIZE);
+
+        let mut count = 0;
+        while let Some(row) = rows.try_next().await? {
+            write_json_line(&mut out, &columns, &row)?;
+            count += 1;
+            if out.len() >= BUFFER_SIZE {
+                write(writer, &mut out).await?;
+            }
+        }
+        write(writer, &mut out).await?;
+        writer.flush().await.map_err(write_error)?;
+        Ok(count)
+    }
+
+    /// Write a Parquet file.
+    ///
+    /// `ARROW_STREAM` results are copied batch by batch; `JSON_ARRAY` results
+    /// are converted using the types in the result manifest. Each row group is
+    /// written out as soon as it is complete.
+    #[cfg(feature = "parquet")]
+    pub async fn parquet<W: AsyncWrite + Unpin>(
+        &self,
+        response: &Response,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        use crate::arrow_stream::{self, ArrowReader};
+        use crate::types::Format;
+        use futures::StreamExt;
+        use parquet::arrow::ArrowWriter;
+
+        const BATCH_ROWS: usize = 8192;
+        const ROW_GROUP_BYTES: usize = 64 * 1024 * 1024;
+
+        let schema = ArrowReader::schema(response)?;
+        let reader = ArrowReader::new(self.client.clone());
+        let mut batches =
+            if response.manifest.as_ref().and_then(|m| m.format) == Some(Format::ArrowStream) {
+                reader.record_batches(response).boxed()
+            } else {
+                let schema = schema.clone();
+                self.rows(response)
+                    .try_chunks(BATCH_ROWS)
+                    .map_err(|e| e.1)
+                    .and_then(move |rows| {
+                        let batch = arrow_stream::batch_from_rows(&schema, &rows);
+                        async move { batch }
+                    })
+                    .boxed()
+            };
+
+        let mut parquet: Option<ArrowWriter<Vec<u8>>> = None;
+        let mut count = 0;
+        while let Some(batch) = batches.try_next().await? {
+            let out = match &mut parquet {
+                Some(out) => out,
+                None => parquet.insert(
+                    ArrowWriter::try_new(Vec::new(), batch.schema(), None)
+                        .map_err(parquet_error)?,
+                ),
+            };
+            out.write(&batch).map_err(parquet_error)?;
+            count += batch.num_rows() as u64;
+            if out.in_progress_size() >= ROW_GROUP_BYTES {
+                out.flush().map_err(parquet_error)?;
+            }
+            write(writer, out.inner_mut()).await?;
+        }
+
+        let mut out = match parquet {
+            Some(out) => out,
+            None => ArrowWriter::try_new(Vec::new(), schema, None).map_err(parquet_error)?,
+        };
+        out.finish().map_err(parquet_error)?;
+        write(writer, out.inner_mut()).await?;
+        writer.flush().await.map_err(write_error)?;
+        Ok(count)
+    }
+
+    fn rows(&self, response: &Response) -> ResultStream {
+        ResultStream::new(self.client.clone(), response).prefetch(self.prefetch)
+    }
+}
+
+/// Write and clear `buffer`.
+async fn write<W: AsyncWrite + Unpin>(writer: &mut W, buffer: &mut Vec<u8>) -> Result<(), Error> {
+    if !buffer.is_empty() {
+        writer.write_all(buffer).await.map_err(write_error)?;
+        buffer.clear();
+    }
+    Ok(())
+}
+
+fn write_error(e: std::io::Error) -> Error {
+    Error::Other(format!("failed to write export: {}", e))
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_error(e: parquet::errors::ParquetError) -> Error {
+    Error::Other(format!("failed to encode Parquet: {}", e))
+}
+
+fn write_csv_record<'a>(out: &mut Vec<u8>, fields: impl Iterator<Item = Option<&'a str>>) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            out.push(b',');
+        }
+        match field {
+            None => {}
+            Some(value) if value.is_empty() || value.contains([',', '"', '\n', '\r']) => {
+                out.push(b'"');
+                out.extend_from_slice(value.replace('"', "\"\"").as_bytes());
+                out.push(b'"');
+            }
+            Some(value) => out.extend_from_slice(value.as_bytes()),
+        }
+    }
+    out.extend_from_slice(b"\r\n");
+}
+
+fn write_json_line(
+    out: &mut Vec<u8>,
+    columns: &[Column],
+    values: &[Option<String>],
+) -> Result<(), Error> {
+    let row = Row::new(columns, values);
+    out.push(b'{');
+    for (i, column) in columns.iter().enumerate() {
+        if i > 0 {
+            out.push(b',');
+        }
+        serde_json::to_writer(&mut *out, &column.name)?;
+        out.push(b':');
+        serde_json::to_writer(&mut *out, &row.get_index::<serde_json::Value>(i)?)?;
+    }
+    out.extend_from_slice(b"}\n");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, type_name: &str) -> Column {
+        Column {
+            name: name.into(),
+            type_name: Some(type_name.into()),
+            type_text: None,
+            type_precision: None,
+            type_scale: None,
+            position: 0,
+        }
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let mut out = Vec::new();
+        let fields = [
+            Some("plain"),
+            Some("a,b"),
+            Some("say \"hi\""),
+            Some("two\nlines"),
+            Some(""),
+            None,
+        ];
+        write_csv_record(&mut out, fields.into_iter());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "plain,\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\",\"\",\r\n"
+        );
+    }
+
+    #[test]
+    fn test_json_line_uses_column_types() {
+        let columns = vec![
+            column("id", "LONG"),
+            column("price", "DECIMAL"),
+            column("tags", "ARRAY"),
+            column("note", "STRING"),
+        ];
+        let values = vec![
+            Some("1".to_string()),
+            Some("9.99".to_string()),
+            Some("[\"x\"]".to_string()),
+            None,
+        ];
+
+        let mut out = Vec::new();
+        write_json_line(&mut out, &columns, &values).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"id\":1,\"price\":\"9.99\",\"tags\":[\"x\"],\"note\":null}\n"
+        );
+    }
+}
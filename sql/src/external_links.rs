@@ -0,0 +1,293 @@
+use crate::types::{ExternalLink, Response};
+use crate::Statements;
+use databricks_core::{Client, Error};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use std::collections::HashMap;
+
+const DEFAULT_CONCURRENCY: usize = 4;
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// The raw bytes of one result chunk downloaded from cloud storage.
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    pub chunk_index: i32,
+    pub row_offset: Option<i64>,
+    pub row_count: Option<i64>,
+    /// Chunk contents in the statement's [`crate::Format`].
+    pub data: Vec<u8>,
+}
+
+impl Chunk {
+    /// Parse a `JSON_ARRAY` chunk into rows.
+    pub fn json_rows(&self) -> Result<Vec<Vec<Option<String>>>, Error> {
+        Ok(serde_json::from_slice(&self.data)?)
+    }
+}
+
+/// Downloads `EXTERNAL_LINKS` result chunks.
+///
+/// Chunks are fetched concurrently but yielded in order. Presigned URLs are
+/// requested without the Databricks `Authorization` header; a chunk whose
+/// download fails (for example because its link expired) gets a fresh link
+/// from `get_result_chunk` and is retried up to `max_attempts` times.
+pub struct Downloader {
+    client: Client,
+    statements: Statements,
+    concurrency: usize,
+    max_attempts: u32,
+}
+
+impl Downloader {
+    pub fn new(client: Client) -> Self {
+        Self {
+            statements: Statements::new(client.clone()),
+            client,
+            concurrency: DEFAULT_CONCURRENCY,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+
+    /// Maximum number of chunks downloaded at once (default 4).
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Attempts per chunk, each with a refreshed link (default 3).
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Stream every chunk of a succeeded statement, in chunk order.
+    pub fn chunks<'a>(
+        &'a self,
+        response: &'a Response,
+    ) -> impl Stream<Item = Result<Chunk, Error>> + 'a {
+        let mut known: HashMap<i32, ExternalLink> = response
+            .result
+            .iter()
+            .flat_map(|r| r.external_links.iter())
+            .map(|l| (l.chunk_index, l.clone()))
+            .collect();
+        let total = response
+            .manifest
+            .as_ref()
+            .and_then(|m| m.total_chunk_count)
+            .unwrap_or(known.len() as i32);
+
+        stream::iter(0..total)
+            .map(move |index| {
+                let link = known.remove(&index);
+                self.download(&response.statement_id, index, link)
+            })
+            .buffered(self.concurrency)
+    }
+
+    /// Download every chunk of a succeeded statement into memory.
+    pub async fn download_all(&self, response: &Response) -> Result<Vec<Chunk>, Error> {
+        self.chunks(response).try_collect().await
+    }
+
+    /// Download one chunk, resolving its link first if not given.
+    pub async fn download(
+        &self,
+        statement_id: &str,
+        chunk_index: i32,
+        link: Option<ExternalLink>,
+    ) -> Result<Chunk, Error> {
+        let mut link = match link {
+            Some(link) => link,
+            None => self.resolve(statement_id, chunk_index).await?,
+        };
+        let mut attempt = 1;
+
+        loop {
+            let headers: Vec<(String, String)> = link
+                .http_headers
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect();
+
+            match self
+                .client
+                .get_external(&link.external_link, &headers)
+                .await
+            {
+                Ok(data) => {
+                    return Ok(Chunk {
+                        chunk_index,
+                        row_offset: link.row_offset,
+                        row_count: link.row_count,
+                        data,
+                    })
+                }
+                Err(_) if attempt < self.max_attempts => {
+                    attempt += 1;
+                    link = self.resolve(statement_id, chunk_index).await?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Request a fresh presigned link for a chunk.
+    async fn resolve(&self, statement_id: &str, chunk_index: i32) -> Result<ExternalLink, Error> {
+        self.statements
+            .get_result_chunk(statement_id, chunk_index)
+            .await?
+            .external_links
+            .into_iter()
+            .find(|l| l.chunk_index == chunk_index)
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "no external link returned for chunk {} of statement {}",
+                    chunk_index, statement_id
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use databricks_core::transport::mock::MockTransport;
+    use serde_json::json;
+
+    const CHUNKS: &str = "/api/2.0/sql/statements/st/result/chunks";
+
+    fn downloader(mock: &MockTransport) -> Downloader {
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        Downloader::new(client)
+    }
+
+    fn link(index: i32, path: &str) -> serde_json::Value {
+        json!({
+            "chunk_index": index,
+            "row_offset": index,
+            "row_count": 1,
+            "external_link": format!("https://storage.example.com{}", path),
+            "http_headers": {"x-ms-blob-type": "BlockBlob"},
+        })
+    }
+
+    fn response(links: Vec<serde_json::Value>, total: i32) -> Response {
+        serde_json::from_value(json!({
+            "statement_id": "st",
+            "status": {"state": "SUCCEEDED"},
+            "manifest": {"format": "JSON_ARRAY", "total_chunk_count": total},
+            "result": {"external_links": links},
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_chunks_in_order_without_authorization() {
+        let mock = MockTransport::new()
+            .on_raw("GET", "/chunk0", 200, b"[[\"0\"]]".to_vec())
+            .on_raw("GET", "/chunk1", 200, b"[[\"1\"]]".to_vec())
+            .on_raw("GET", "/chunk2", 200, b"[[\"2\"]]".to_vec())
+            .on(
+                "GET",
+                &format!("{}/1", CHUNKS),
+                200,
+                json!({"external_links": [link(1, "/chunk1")]}),
+            )
+            .on(
+                "GET",
+                &format!("{}/2", CHUNKS),
+                200,
+                json!({"external_links": [link(2, "/chunk2")]}),
+            );
+
+        let chunks = downloader(&mock)
+            .concurrency(3)
+            .download_all(&response(vec![link(0, "/chunk0")], 3))
+            .await
+            .unwrap();
+        let rows: Vec<_> = chunks
+            .iter()
+            .map(|c| (c.chunk_index, c.row_offset, c.json_rows().unwrap()))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                (0, Some(0), vec![vec![Some("0".to_string())]]),
+                (1, Some(1), vec![vec![Some("1".to_string())]]),
+                (2, Some(2), vec![vec![Some("2".to_string())]]),
+            ]
+        );
+
+        for request in mock.requests() {
+            if request.path.starts_with("/chunk") {
+                assert_eq!(request.header("Authorization"), None);
+                assert_eq!(request.header("x-ms-blob-type"), Some("BlockBlob"));
+            } else {
+                assert_eq!(request.header("Authorization"), Some("Bearer token"));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expired_link_is_refreshed() {
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                "/expired",
+                403,
+                json!({"message": "Request has expired"}),
+            )
+            .on_raw("GET", "/fresh", 200, b"[[\"0\"]]".to_vec())
+            .on(
+                "GET",
+                &format!("{}/0", CHUNKS),
+                200,
+                json!({"external_links": [link(0, "/fresh")]}),
+            );
+
+        let chunks = downloader(&mock)
+            .download_all(&response(vec![link(0, "/expired")], 1))
+            .await
+            .unwrap();
+        assert_eq!(chunks[0].json_rows().unwrap(), [[Some("0".to_string())]]);
+
+        let paths: Vec<_> = mock.requests().into_iter().map(|r| r.path).collect();
+        assert_eq!(paths, ["/expired", &format!("{}/0", CHUNKS), "/fresh"]);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                "/expired",
+                403,
+                json!({"message": "Request has expired"}),
+            )
+            .on(
+                "GET",
+                &format!("{}/0", CHUNKS),
+                200,
+                json!({"external_links": [link(0, "/expired")]}),
+            );
+
+        let result = downloader(&mock)
+            .max_attempts(3)
+            .download_all(&response(vec![link(0, "/expired")], 1))
+            .await;
+        assert!(result.is_err());
+
+        let requests = mock.requests();
+        let downloads = requests.iter().filter(|r| r.path == "/expired").count();
+        let refreshes = requests
+            .iter()
+            .filter(|r| r.path.starts_with(CHUNKS))
+            .count();
+        assert_eq!((downloads, refreshes), (3, 2));
+    }
+}
@@ -1,23 +1,60 @@
 mod alert_types;
 mod alerts;
+#[cfg(feature = "arrow")]
+mod arrow_stream;
+mod assets;
 mod catalog;
+mod export;
+mod external_links;
+mod loader;
+mod migrate;
+mod parameters;
 mod queries;
 mod query_history;
 mod query_history_types;
+mod query_monitor;
 mod query_types;
 mod query_visualizations;
+mod result_stream;
+mod rows;
+mod script;
+mod statement_handle;
 mod statements;
 mod types;
 mod warehouses;
 
 pub use alert_types::*;
 pub use alerts::Alerts;
-pub use catalog::{Catalog, Column, Table};
+#[cfg(feature = "arrow")]
+pub use arrow_stream::{
+    batch_from_rows, decode as decode_arrow, schema as arrow_schema, ArrowReader,
+};
+pub use assets::{
+    AlertSpec, AssetKind, BundleFormat, QueryBundle, SqlAssets, SyncAction, SyncChange, SyncReport,
+    VisualizationSpec, BUNDLE_VERSION,
+};
+pub use catalog::{Catalog, Column, ForeignKey, PrimaryKey, Table, TableDetails};
+pub use export::{ExportFormat, Exporter};
+pub use external_links::{Chunk, Downloader};
+pub use loader::{FileFormat, LoadMode, LoadReport, Loader, Source};
+pub use migrate::{AppliedMigration, Migration, MigrationReport, Migrator};
+pub use parameters::{quote_identifier, quote_name, quote_string, ParameterValue};
 pub use queries::Queries;
 pub use query_history::QueryHistory;
 pub use query_history_types::*;
+pub use query_monitor::{QueryEvent, QueryWatcher};
 pub use query_types::*;
 pub use query_visualizations::QueryVisualizations;
+pub use result_stream::ResultStream;
+pub use rows::{FromRow, Row};
+pub use script::{
+    split_script, ErrorPolicy, ScriptReport, ScriptRunner, ScriptStatement, StatementOutcome,
+};
+pub use statement_handle::StatementHandle;
 pub use statements::Statements;
 pub use types::*;
 pub use warehouses::Warehouses;
+
+/// The `arrow` crate version used by [`ArrowReader`].
+#[cfg(feature = "arrow")]
+pub use ::arrow;
@@ -0,0 +1,444 @@
+use crate::parameters::{quote_name, quote_string};
+use crate::types::Request;
+use crate::Statements;
+use databricks_core::runtime::{self, Runtime};
+use databricks_core::{Client, Error};
+use databricks_files::Files;
+use futures::TryStreamExt;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Format of the files being loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FileFormat {
+    Csv,
+    Json,
+    Parquet,
+}
+
+impl FileFormat {
+    fn as_str(&self) -> &'static str {
+        match self {
+            FileFormat::Csv => "CSV",
+            FileFormat::Json => "JSON",
+            FileFormat::Parquet => "PARQUET",
+        }
+    }
+
+    fn default_options(&self) -> Vec<(String, String)> {
+        match self {
+            FileFormat::Csv => vec![
+                ("header".into(), "true".into()),
+                ("inferSchema".into(), "true".into()),
+            ],
+            FileFormat::Json | FileFormat::Parquet => Vec::new(),
+        }
+    }
+}
+
+/// How staged files are written to the target table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadMode {
+    /// `COPY INTO` an existing table. Files already loaded into the table are
+    /// skipped by the warehouse.
+    CopyInto,
+    /// `CREATE TABLE ... AS SELECT * FROM read_files(...)`, replacing the
+    /// table if `replace` is set.
+    CreateTable { replace: bool },
+}
+
+/// A local file to load.
+#[derive(Debug, Clone)]
+pub enum Source {
+    Path(PathBuf),
+    Bytes { name: String, data: Vec<u8> },
+}
+
+impl Source {
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        Source::Path(path.into())
+    }
+
+    pub fn bytes(name: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        Source::Bytes {
+            name: name.into(),
+            data: data.into(),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Source::Path(path) => path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "data".into()),
+            Source::Bytes { name, .. } => name.clone(),
+        }
+    }
+
+    /// File name for the staged copy, with anything but letters, digits,
+    /// `.`, `-` and `_` replaced so that it is a plain path segment.
+    fn staged_name(&self) -> String {
+        self.name()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    /// Read the file through the runtime's blocking hook so that large files
+    /// don't stall the executor.
+    async fn read(&self, runtime: &dyn Runtime) -> Result<Vec<u8>, Error> {
+        match self {
+            Source::Path(path) => read_file(runtime, path.clone()).await,
+            Source::Bytes { data, .. } => Ok(data.clone()),
+        }
+    }
+}
+
+/// Read `path` through [`Runtime::spawn_blocking`].
+pub(crate) async fn read_file(runtime: &dyn Runtime, path: PathBuf) -> Result<Vec<u8>, Error> {
+    runtime::blocking(runtime, move || {
+        std::fs::read(&path)
+            .map_err(|e| Error::Other(format!("failed to read {}: {}", path.display(), e)))
+    })
+    .await?
+}
+
+/// Outcome of a [`Loader::load`].
+#[derive(Debug, Clone)]
+pub struct LoadReport {
+    /// Rows written to the table, as reported by the warehouse.
+    pub rows_loaded: i64,
+    /// Volume paths the files were staged to.
+    pub staged_files: Vec<String>,
+    /// Whether the staged files were removed afterwards.
+    pub cleaned_up: bool,
+}
+
+/// Loads local files into a Delta table through a SQL warehouse.
+///
+/// Files are staged to a fresh directory under a Unity Catalog volume, loaded
+/// with `COPY INTO` (or `CREATE TABLE AS` over `read_files`), and removed
+/// again once the statement finishes, whether or not it succeeded.
+///
+/// ```no_run
+/// # async fn run(client: databricks_core::Client) -> Result<(), databricks_core::Error> {
+/// use databricks_sql::{FileFormat, Loader, Source};
+///
+/// let report = Loader::new(client, "wh-id", "/Volumes/main/staging/uploads")
+///     .format(FileFormat::Csv)
+///     .load(&["main", "sales", "orders"], &[Source::path("orders.csv")])
+///     .await?;
+/// println!("loaded {} rows", report.rows_loaded);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Loader {
+    statements: Statements,
+    runtime: Arc<dyn Runtime>,
+    files: Files,
+    warehouse_id: String,
+    volume_path: String,
+    format: FileFormat,
+    format_options: Option<Vec<(String, String)>>,
+    copy_options: Vec<(String, String)>,
+    mode: LoadMode,
+    cleanup: bool,
+    timeout: Duration,
+}
+
+impl Loader {
+    /// `volume_path` is a directory in a volume, e.g.
+    /// `/Volumes/main/staging/uploads`, that the warehouse can read.
+    pub fn new(
+        client: Client,
+        warehouse_id: impl Into<String>,
+        volume_path: impl Into<String>,
+    ) -> Self {
+        Self {
+            statements: Statements::new(client.clone()),
+            runtime: client.runtime().clone(),
+            files: Files::new(client),
+            warehouse_id: warehouse_id.into(),
+            volume_path: volume_path.into().trim_end_matches('/').to_string(),
+            format: FileFormat::Csv,
+            format_options: None,
+            copy_options: Vec::new(),
+            mode: LoadMode::CopyInto,
+            cleanup: true,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn format(mut self, format: FileFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set a reader option such as `delimiter` or `multiLine`.
+    ///
+    /// The first call replaces the defaults (`header` and `inferSchema` for
+    /// CSV).
+    pub fn format_option(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.format_options
+            .get_or_insert_with(Vec::new)
+            .push((key.into(), value.into()));
+        self
+    }
+
+    /// Set a `COPY_OPTIONS` entry such as `mergeSchema`.
+    pub fn copy_option(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.copy_options.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn mode(mut self, mode: LoadMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Leave staged files in the volume after loading.
+    pub fn keep_staged_files(mut self) -> Self {
+        self.cleanup = false;
+        self
+    }
+
+    /// Maximum time to wait for the load statement (default 30 minutes).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Stage `sources` and load them into `table` (`[catalog, schema, table]`).
+    pub async fn load(&self, table: &[&str], sources: &[Source]) -> Result<LoadReport, Error> {
+        let directory = format!("{}/load-{:016x}", self.volume_path, rand::random::<u64>());
+        let statement = self.statement(table, &directory)?;
+        self.files.create_directory(&directory).await?;
+
+        let mut staged_files = Vec::with_capacity(sources.len());
+        let result = self
+            .stage_and_load(&directory, statement, sources, &mut staged_files)
+            .await;
+
+        let cleaned_up = self.cleanup && self.remove(&directory, &staged_files).await;
+
+        Ok(LoadReport {
+            rows_loaded: result?,
+            staged_files,
+            cleaned_up,
+        })
+    }
+
+    async fn stage_and_load(
+        &self,
+        directory: &str,
+        statement: String,
+        sources: &[Source],
+        staged_files: &mut Vec<String>,
+    ) -> Result<i64, Error> {
+        for (i, source) in sources.iter().enumerate() {
+            let path = format!("{}/{:05}-{}", directory, i, source.staged_name());
+            self.files
+                .upload(&path, &source.read(self.runtime.as_ref()).await?)
+                .await?;
+            staged_files.push(path);
+        }
+
+        let request = Request::new(statement, &self.warehouse_id);
+        let response = self
+            .statements
+            .execute_wait(&request, POLL_INTERVAL, self.timeout)
+            .await?;
+
+        let counts: Vec<Counts> = self.statements.rows(&response).try_collect().await?;
+        Ok(counts
+            .iter()
+            .map(|c| c.num_inserted_rows.or(c.num_affected_rows).unwrap_or(0))
+            .sum())
+    }
+
+    /// Best-effort removal of the staging directory.
+    async fn remove(&self, directory: &str, staged_files: &[String]) -> bool {
+        let mut removed = true;
+        for path in staged_files {
+            removed &= self.files.delete(path).await.is_ok();
+        }
+        removed && self.files.delete_directory(directory).await.is_ok()
+    }
+
+    fn statement(&self, table: &[&str], directory: &str) -> Result<String, Error> {
+        let table = quote_name(table);
+        let directory = quote_string(directory);
+        let format_options = self
+            .format_options
+            .clone()
+            .unwrap_or_else(|| self.format.default_options());
+
+        match self.mode {
+            LoadMode::CopyInto => {
+                let mut statement = format!(
+                    "COPY INTO {} FROM {} FILEFORMAT = {}",
+                    table,
+                    directory,
+                    self.format.as_str()
+                );
+                if !format_options.is_empty() {
+                    statement.push_str(&format!(
+                        " FORMAT_OPTIONS ({})",
+                        option_list(&format_options)
+                    ));
+                }
+                if !self.copy_options.is_empty() {
+                    statement.push_str(&format!(
+                        " COPY_OPTIONS ({})",
+                        option_list(&self.copy_options)
+                    ));
+                }
+                Ok(statement)
+            }
+            LoadMode::CreateTable { replace } => {
+                let mut arguments = vec![
+                    directory,
+                    format!(
+                        "format => {}",
+                        quote_string(&self.format.as_str().to_lowercase())
+                    ),
+                ];
+                arguments.extend(named_arguments(&format_options)?);
+                Ok(format!(
+                    "CREATE {}TABLE {} AS SELECT * FROM read_files({})",
+                    if replace { "OR REPLACE " } else { "" },
+                    table,
+                    arguments.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+/// `'key' = 'value'` pairs for `COPY INTO`.
+fn option_list(options: &[(String, String)]) -> String {
+    options
+        .iter()
+        .map(|(key, value)| format!("{} = {}", quote_string(key), quote_string(value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `key => 'value'` named arguments for `read_files`.
+///
+/// Argument names can't be quoted, so keys must match
+/// `[A-Za-z_][A-Za-z0-9_.]*`.
+fn named_arguments(options: &[(String, String)]) -> Result<Vec<String>, Error> {
+    options
+        .iter()
+        .map(|(key, value)| {
+            let mut chars = key.chars();
+            let valid = chars
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            if !valid {
+                return Err(Error::Other(format!(
+                    "invalid read_files option name: {:?}",
+                    key
+                )));
+            }
+            Ok(format!("{} => {}", key, quote_string(value)))
+        })
+        .collect()
+}
+
+/// Row counts returned by `COPY INTO` and `CREATE TABLE AS`.
+#[derive(Deserialize)]
+struct Counts {
+    num_affected_rows: Option<i64>,
+    num_inserted_rows: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loader() -> Loader {
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(databricks_core::transport::mock::MockTransport::new())
+            .build()
+            .unwrap();
+        Loader::new(client, "wh", "/Volumes/main/staging/uploads/")
+    }
+
+    #[test]
+    fn test_copy_into_statement() {
+        let loader = loader()
+            .format_option("delimiter", "|")
+            .copy_option("mergeSchema", "true");
+        assert_eq!(
+            loader
+                .statement(
+                    &["main", "sales", "orders"],
+                    "/Volumes/main/staging/uploads/load-1"
+                )
+                .unwrap(),
+            "COPY INTO `main`.`sales`.`orders` FROM '/Volumes/main/staging/uploads/load-1' \
+             FILEFORMAT = CSV FORMAT_OPTIONS ('delimiter' = '|') \
+             COPY_OPTIONS ('mergeSchema' = 'true')"
+        );
+    }
+
+    #[test]
+    fn test_create_table_statement() {
+        let loader = loader()
+            .mode(LoadMode::CreateTable { replace: true })
+            .format_option("header", "true")
+            .format_option("dateFormat", "d/M/y");
+        assert_eq!(
+            loader
+                .statement(&["main", "sales", "orders"], "/dir")
+                .unwrap(),
+            "CREATE OR REPLACE TABLE `main`.`sales`.`orders` AS SELECT * FROM \
+             read_files('/dir', format => 'csv', header => 'true', dateFormat => 'd/M/y')"
+        );
+        assert!(loader
+            .format_option("x) --", "1")
+            .statement(&["t"], "/dir")
+            .is_err());
+    }
+
+    #[test]
+    fn test_staged_name() {
+        assert_eq!(
+            Source::bytes("my data?#1.csv", "").staged_name(),
+            "my_data__1.csv"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_path_through_runtime() {
+        use databricks_core::runtime::tokio::TokioRuntime;
+
+        let path = std::env::temp_dir().join(format!("loader-{:016x}.csv", rand::random::<u64>()));
+        std::fs::write(&path, "a,b\n1,2\n").unwrap();
+
+        let data = Source::path(&path).read(&TokioRuntime).await.unwrap();
+        assert_eq!(data, b"a,b\n1,2\n");
+        std::fs::remove_file(&path).unwrap();
+
+        let missing = Source::path(&path).read(&TokioRuntime).await.unwrap_err();
+        assert!(missing.to_string().contains("failed to read"));
+    }
+}
@@ -0,0 +1,745 @@
+use crate::parameters::{quote_name, quote_string};
+use crate::rows::FromRow;
+use crate::script::ScriptRunner;
+use crate::types::{Request, Response};
+use crate::Statements;
+use databricks_core::runtime::Instant;
+use databricks_core::Error;
+use futures::TryStreamExt;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const TIMEOUT: Duration = Duration::from_secs(30 * 60);
+const LOCK_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// A versioned SQL script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    pub version: u64,
+    pub name: String,
+    pub sql: String,
+    /// Hex SHA-256 of the script, ignoring line ending style.
+    pub checksum: String,
+}
+
+impl Migration {
+    pub fn new(version: u64, name: impl Into<String>, sql: impl Into<String>) -> Self {
+        let sql = sql.into();
+        Self {
+            version,
+            name: name.into(),
+            checksum: checksum(&sql),
+            sql,
+        }
+    }
+
+    /// Load every `.sql` file in `dir`, ordered by version.
+    ///
+    /// File names start with the version, optionally prefixed with `V`,
+    /// followed by `_`, `__` or `-` and a description:
+    /// `V001__create_orders.sql` or `2_add_index.sql`.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Vec<Self>, Error> {
+        let dir = dir.as_ref();
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| Error::Other(format!("failed to read {}: {}", dir.display(), e)))?;
+
+        let mut migrations = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|e| Error::Other(format!("failed to read {}: {}", dir.display(), e)))?
+                .path();
+            if path.extension().and_then(|e| e.to_str()) != Some("sql") {
+                continue;
+            }
+            let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+            let (version, name) = parse_file_name(stem).ok_or_else(|| {
+                Error::Other(format!(
+                    "migration file name has no version: {}",
+                    path.display()
+                ))
+            })?;
+            let sql = std::fs::read_to_string(&path)
+                .map_err(|e| Error::Other(format!("failed to read {}: {}", path.display(), e)))?;
+            migrations.push(Self::new(version, name, sql));
+        }
+        migrations.sort_by_key(|m| m.version);
+        Ok(migrations)
+    }
+}
+
+/// A migration run, or to be run in a dry run, by [`Migrator::migrate`].
+#[derive(Debug, Clone)]
+pub struct AppliedMigration {
+    pub version: u64,
+    pub name: String,
+    /// Zero in a dry run.
+    pub elapsed: Duration,
+}
+
+/// Outcome of a [`Migrator::migrate`].
+#[derive(Debug, Clone, Default)]
+pub struct MigrationReport {
+    pub dry_run: bool,
+    /// Versions applied by earlier runs.
+    pub already_applied: Vec<u64>,
+    /// Migrations applied by this run, or that would be in a dry run.
+    pub applied: Vec<AppliedMigration>,
+}
+
+/// Applies versioned SQL migrations to a warehouse, once each, in order.
+///
+/// Applied versions are recorded with their checksums in a history table.
+/// Migrations already applied are checked against their recorded checksum,
+/// so editing one after the fact is an error rather than silently ignored.
+/// A migration is a script as run by [`ScriptRunner`]; Delta DDL is not
+/// transactional, so a migration that fails part way is not rolled back and
+/// must be fixed by hand.
+///
+/// Concurrent runs are excluded by a lock row in a second table,
+/// `<history>_lock`, taken with a conditional `MERGE`. A lock left behind
+/// by a crashed run expires after an hour (see [`Migrator::lock_ttl`]) or
+/// can be removed with [`Migrator::force_unlock`].
+///
+/// ```no_run
+/// # async fn run(statements: databricks_sql::Statements) -> Result<(), databricks_core::Error> {
+/// use databricks_sql::{Migration, Migrator};
+///
+/// let migrations = Migration::from_dir("migrations")?;
+/// let report = Migrator::new(statements, "wh-id", &["main", "ops", "schema_migrations"])?
+///     .migrate(&migrations)
+///     .await?;
+/// for m in &report.applied {
+///     println!("applied {} {} in {:?}", m.version, m.name, m.elapsed);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Migrator {
+    statements: Statements,
+    warehouse_id: String,
+    table: Vec<String>,
+    catalog: Option<String>,
+    schema: Option<String>,
+    dry_run: bool,
+    lock_ttl: Duration,
+    timeout: Duration,
+}
+
+impl Migrator {
+    /// `table` is the history table as `[catalog, schema, table]`; it and the
+    /// lock table are created on first use.
+    ///
+    /// Fails if `table` is empty or has an empty part.
+    pub fn new(
+        statements: Statements,
+        warehouse_id: impl Into<String>,
+        table: &[&str],
+    ) -> Result<Self, Error> {
+        if table.is_empty() || table.iter().any(|p| p.is_empty()) {
+            return Err(Error::Other(format!(
+                "invalid migration history table: {:?}",
+                table
+            )));
+        }
+        Ok(Self {
+            statements,
+            warehouse_id: warehouse_id.into(),
+            table: table.iter().map(|p| p.to_string()).collect(),
+            catalog: None,
+            schema: None,
+            dry_run: false,
+            lock_ttl: LOCK_TTL,
+            timeout: TIMEOUT,
+        })
+    }
+
+    /// Catalog each migration starts in.
+    pub fn catalog(mut self, catalog: impl Into<String>) -> Self {
+        self.catalog = Some(catalog.into());
+        self
+    }
+
+    /// Schema each migration starts in.
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Report pending migrations without running them or taking the lock.
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Age after which another run may take over the lock (default 1 hour).
+    pub fn lock_ttl(mut self, ttl: Duration) -> Self {
+        self.lock_ttl = ttl;
+        self
+    }
+
+    /// Maximum time to wait for each statement (default 30 minutes).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Apply the migrations that have not been applied yet.
+    ///
+    /// Fails without running anything if two migrations share a version, an
+    /// applied migration's checksum changed, or a pending migration is older
+    /// than the latest applied one.
+    pub async fn migrate(&self, migrations: &[Migration]) -> Result<MigrationReport, Error> {
+        let mut report = MigrationReport {
+            dry_run: self.dry_run,
+            ..Default::default()
+        };
+
+        if self.dry_run {
+            let applied = if self.history_exists().await? {
+                self.applied().await?
+            } else {
+                BTreeMap::new()
+            };
+            let pending = plan(migrations, &applied)?;
+            report.already_applied = applied.into_keys().collect();
+            report.applied = pending
+                .into_iter()
+                .map(|m| AppliedMigration {
+                    version: m.version,
+                    name: m.name.clone(),
+                    elapsed: Duration::ZERO,
+                })
+                .collect();
+            return Ok(report);
+        }
+
+        self.create_tables().await?;
+        let token = format!("{:016x}", rand::random::<u64>());
+        self.lock(&token).await?;
+        let result = self.apply(migrations, &mut report).await;
+        let unlocked = self.unlock(&token).await;
+        result?;
+        unlocked?;
+        Ok(report)
+    }
+
+    /// Remove the lock regardless of who holds it.
+    pub async fn force_unlock(&self) -> Result<(), Error> {
+        self.execute(self.request(format!("DELETE FROM {} WHERE id = 1", self.lock_table())))
+            .await?;
+        Ok(())
+    }
+
+    async fn apply(
+        &self,
+        migrations: &[Migration],
+        report: &mut MigrationReport,
+    ) -> Result<(), Error> {
+        let applied = self.applied().await?;
+        let pending = plan(migrations, &applied)?;
+        report.already_applied = applied.into_keys().collect();
+
+        for migration in pending {
+            let start = Instant::now();
+            let mut runner = ScriptRunner::new(self.statements.clone(), &self.warehouse_id)
+                .timeout(self.timeout)
+                .poll_interval(POLL_INTERVAL);
+            if let Some(catalog) = &self.catalog {
+                runner = runner.catalog(catalog);
+            }
+            if let Some(schema) = &self.schema {
+                runner = runner.schema(schema);
+            }
+
+            let script = runner.run(&migration.sql).await?;
+            if let Some((statement, e)) = script.failures().next() {
+                return Err(Error::Other(format!(
+                    "migration {} ({}) failed at line {}: {}",
+                    migration.version, migration.name, statement.line, e
+                )));
+            }
+
+            let elapsed = start.elapsed();
+            let version = i64::try_from(migration.version).map_err(|_| {
+                Error::Other(format!(
+                    "migration version {} is too large",
+                    migration.version
+                ))
+            })?;
+            self.execute(
+                self.request(format!(
+                    "INSERT INTO {} (version, name, checksum, applied_at, applied_by, execution_ms) \
+                     VALUES (:version, :name, :checksum, current_timestamp(), current_user(), :execution_ms)",
+                    self.history_table()
+                ))
+                .bind("version", version)
+                .bind("name", migration.name.as_str())
+                .bind("checksum", migration.checksum.as_str())
+                .bind("execution_ms", elapsed.as_millis() as i64),
+            )
+            .await?;
+
+            report.applied.push(AppliedMigration {
+                version: migration.version,
+                name: migration.name.clone(),
+                elapsed,
+            });
+        }
+        Ok(())
+    }
+
+    async fn create_tables(&self) -> Result<(), Error> {
+        self.execute(self.request(format!(
+            "CREATE TABLE IF NOT EXISTS {} (version BIGINT NOT NULL, name STRING, \
+             checksum STRING, applied_at TIMESTAMP, applied_by STRING, execution_ms BIGINT)",
+            self.history_table()
+        )))
+        .await?;
+        self.execute(self.request(format!(
+            "CREATE TABLE IF NOT EXISTS {} (id INT NOT NULL, token STRING, owner STRING, \
+             acquired_at TIMESTAMP)",
+            self.lock_table()
+        )))
+        .await?;
+        Ok(())
+    }
+
+    async fn history_exists(&self) -> Result<bool, Error> {
+        let Some((name, schema)) = self.table.split_last() else {
+            return Err(Error::Other("migration history table has no name".into()));
+        };
+        let schema: Vec<&str> = schema.iter().map(String::as_str).collect();
+        let statement = if schema.is_empty() {
+            format!("SHOW TABLES LIKE {}", quote_string(name))
+        } else {
+            format!(
+                "SHOW TABLES IN {} LIKE {}",
+                quote_name(&schema),
+                quote_string(name)
+            )
+        };
+        let response = self.execute(self.request(statement)).await?;
+        let rows = response.manifest.and_then(|m| m.total_row_count);
+        Ok(rows.unwrap_or(0) > 0)
+    }
+
+    async fn applied(&self) -> Result<BTreeMap<u64, AppliedRow>, Error> {
+        let rows: Vec<AppliedRow> = self
+            .query(self.request(format!(
+                "SELECT version, name, checksum FROM {} ORDER BY version",
+                self.history_table()
+            )))
+            .await?;
+        rows.into_iter()
+            .map(|r| {
+                let version = u64::try_from(r.version).map_err(|_| {
+                    Error::Other(format!("invalid applied migration version {}", r.version))
+                })?;
+                Ok((version, r))
+            })
+            .collect()
+    }
+
+    /// Take the lock, or fail if another run holds it.
+    async fn lock(&self, token: &str) -> Result<(), Error> {
+        // A concurrent MERGE on the same row fails with a conflict; either way
+        // the row read back says who won.
+        let merged = self
+            .execute(
+                self.request(format!(
+                    "MERGE INTO {} AS l USING (SELECT 1 AS id) AS s ON l.id = s.id \
+                     WHEN MATCHED AND l.acquired_at < timestampadd(SECOND, -:ttl, current_timestamp()) \
+                     THEN UPDATE SET token = :token, owner = current_user(), acquired_at = current_timestamp() \
+                     WHEN NOT MATCHED THEN INSERT (id, token, owner, acquired_at) \
+                     VALUES (1, :token, current_user(), current_timestamp())",
+                    self.lock_table()
+                ))
+                .bind("token", token)
+                .bind("ttl", self.lock_ttl.as_secs() as i64),
+            )
+            .await;
+
+        // The lock table has no uniqueness constraint, so two runs whose
+        // MERGEs both found no row each insert one. Seeing more than one row
+        // means contention: back out and let neither run proceed.
+        let holders: Vec<LockRow> = self
+            .query(self.request(format!(
+                "SELECT token, owner, CAST(acquired_at AS STRING) AS acquired_at FROM {} WHERE id = 1",
+                self.lock_table()
+            )))
+            .await?;
+
+        match holders.as_slice() {
+            [holder] if holder.token.as_deref() == Some(token) => Ok(()),
+            [] => Err(merged
+                .err()
+                .unwrap_or_else(|| Error::Other("failed to take the migration lock".into()))),
+            [_, _, ..] if holders.iter().any(|h| h.token.as_deref() == Some(token)) => {
+                self.unlock(token).await?;
+                Err(Error::Other(
+                    "another migration run took the lock at the same time; retry".into(),
+                ))
+            }
+            [holder, ..] => Err(Error::Other(format!(
+                "migrations are locked by {} since {}",
+                holder.owner.as_deref().unwrap_or("unknown"),
+                holder.acquired_at.as_deref().unwrap_or("unknown"),
+            ))),
+        }
+    }
+
+    async fn unlock(&self, token: &str) -> Result<(), Error> {
+        self.execute(
+            self.request(format!(
+                "DELETE FROM {} WHERE id = 1 AND token = :token",
+                self.lock_table()
+            ))
+            .bind("token", token),
+        )
+        .await?;
+        Ok(())
+    }
+
+    fn history_table(&self) -> String {
+        let parts: Vec<&str> = self.table.iter().map(String::as_str).collect();
+        quote_name(&parts)
+    }
+
+    fn lock_table(&self) -> String {
+        let mut parts: Vec<String> = self.table.clone();
+        if let Some(name) = parts.last_mut() {
+            name.push_str("_lock");
+        }
+        let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+        quote_name(&parts)
+    }
+
+    fn request(&self, sql: impl Into<String>) -> Request {
+        Request::new(sql, &self.warehouse_id)
+    }
+
+    async fn execute(&self, request: Request) -> Result<Response, Error> {
+        self.statements
+            .execute_wait(&request, POLL_INTERVAL, self.timeout)
+            .await
+    }
+
+    async fn query<T: FromRow>(&self, request: Request) -> Result<Vec<T>, Error> {
+        let response = self.execute(request).await?;
+        self.statements.rows(&response).try_collect().await
+    }
+}
+
+#[derive(Deserialize)]
+struct AppliedRow {
+    version: i64,
+    name: Option<String>,
+    checksum: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LockRow {
+    token: Option<String>,
+    owner: Option<String>,
+    acquired_at: Option<String>,
+}
+
+/// Validate `migrations` against the history and return those to apply.
+fn plan<'a>(
+    migrations: &'a [Migration],
+    applied: &BTreeMap<u64, AppliedRow>,
+) -> Result<Vec<&'a Migration>, Error> {
+    let mut versions = BTreeSet::new();
+    for migration in migrations {
+        if !versions.insert(migration.version) {
+            return Err(Error::Other(format!(
+                "duplicate migration version {}",
+                migration.version
+            )));
+        }
+    }
+
+    let latest = applied.keys().next_back().copied();
+    let mut pending = Vec::new();
+    for migration in migrations {
+        match applied.get(&migration.version) {
+            Some(row) if row.checksum.as_deref() != Some(migration.checksum.as_str()) => {
+                return Err(Error::Other(format!(
+                    "migration {} ({}) was changed after it was applied as {}",
+                    migration.version,
+                    migration.name,
+                    row.name.as_deref().unwrap_or("unknown"),
+                )));
+            }
+            Some(_) => {}
+            None if latest.is_some_and(|latest| migration.version < latest) => {
+                return Err(Error::Other(format!(
+                    "migration {} ({}) is older than the latest applied version {}",
+                    migration.version,
+                    migration.name,
+                    latest.unwrap_or_default(),
+                )));
+            }
+            None => pending.push(migration),
+        }
+    }
+    pending.sort_by_key(|m| m.version);
+    Ok(pending)
+}
+
+fn parse_file_name(stem: &str) -> Option<(u64, String)> {
+    let rest = stem
+        .strip_prefix(['V', 'v'])
+        .filter(|r| r.starts_with(|c: char| c.is_ascii_digit()))
+        .unwrap_or(stem);
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let version = rest[..digits].parse().ok()?;
+    let name = rest[digits..]
+        .trim_start_matches(['_', '-'])
+        .replace('_', " ");
+    Some((version, name))
+}
+
+fn checksum(sql: &str) -> String {
+    let digest = Sha256::digest(sql.replace("\r\n", "\n").as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use databricks_core::transport::mock::MockTransport;
+    use serde_json::json;
+
+    const STATEMENTS: &str = "/api/2.0/sql/statements";
+
+    fn applied(version: u64, sql: &str) -> (u64, AppliedRow) {
+        let row = AppliedRow {
+            version: version as i64,
+            name: Some(format!("m{}", version)),
+            checksum: Some(checksum(sql)),
+        };
+        (version, row)
+    }
+
+    #[test]
+    fn test_plan() {
+        let migrations = vec![
+            Migration::new(1, "one", "CREATE TABLE a (id INT);\n"),
+            Migration::new(2, "two", "ALTER TABLE a ADD COLUMN b INT;\n"),
+            Migration::new(3, "three", "SELECT 1"),
+        ];
+
+        let history = BTreeMap::from([applied(1, "CREATE TABLE a (id INT);\r\n")]);
+        let pending: Vec<u64> = plan(&migrations, &history)
+            .unwrap()
+            .iter()
+            .map(|m| m.version)
+            .collect();
+        assert_eq!(pending, [2, 3]);
+
+        let history = BTreeMap::from([applied(1, "CREATE TABLE a (id BIGINT);")]);
+        assert!(plan(&migrations, &history).is_err());
+
+        let history = BTreeMap::from([
+            applied(1, "CREATE TABLE a (id INT);\n"),
+            applied(3, "SELECT 1"),
+        ]);
+        assert!(plan(&migrations, &history).is_err());
+    }
+
+    #[test]
+    fn test_parse_file_name() {
+        assert_eq!(
+            parse_file_name("V001__create_orders"),
+            Some((1, "create orders".into()))
+        );
+        assert_eq!(
+            parse_file_name("20_add-index"),
+            Some((20, "add-index".into()))
+        );
+        assert_eq!(parse_file_name("readme"), None);
+    }
+
+    fn migrator(mock: &MockTransport) -> Migrator {
+        let client = databricks_core::Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        Migrator::new(Statements::new(client), "wh", &["main", "ops", "history"]).unwrap()
+    }
+
+    /// A succeeded statement returning `rows` of `columns`.
+    fn result(columns: &[&str], rows: serde_json::Value) -> serde_json::Value {
+        let count = rows.as_array().map_or(0, Vec::len);
+        json!({
+            "statement_id": "st",
+            "status": {"state": "SUCCEEDED"},
+            "manifest": {
+                "format": "JSON_ARRAY",
+                "total_chunk_count": 1,
+                "total_row_count": count,
+                "schema": {"columns": columns
+                    .iter()
+                    .map(|c| json!({"name": c, "type_name": if *c == "version" { "LONG" } else { "STRING" }}))
+                    .collect::<Vec<_>>()},
+            },
+            "result": {"chunk_index": 0, "data_array": rows},
+        })
+    }
+
+    fn statements_sent(mock: &MockTransport) -> Vec<String> {
+        mock.requests()
+            .into_iter()
+            .filter_map(|r| r.body?.get("statement")?.as_str().map(String::from))
+            .collect()
+    }
+
+    #[test]
+    fn test_new_rejects_empty_table() {
+        let mock = MockTransport::new();
+        let client = databricks_core::Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock)
+            .build()
+            .unwrap();
+        assert!(Migrator::new(Statements::new(client.clone()), "wh", &[]).is_err());
+        assert!(Migrator::new(Statements::new(client), "wh", &["main", ""]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_and_changed_checksum() {
+        let migrations = vec![
+            Migration::new(1, "one", "CREATE TABLE a (id INT);\n"),
+            Migration::new(2, "two", "ALTER TABLE a ADD COLUMN b INT;\n"),
+        ];
+        let history = |sql: &str| {
+            result(
+                &["version", "name", "checksum"],
+                json!([["1", "one", checksum(sql)]]),
+            )
+        };
+        let mock = MockTransport::new()
+            .on(
+                "POST",
+                STATEMENTS,
+                200,
+                result(&["tableName"], json!([["history"]])),
+            )
+            .on(
+                "POST",
+                STATEMENTS,
+                200,
+                history("CREATE TABLE a (id INT);\n"),
+            );
+
+        let report = migrator(&mock)
+            .dry_run()
+            .migrate(&migrations)
+            .await
+            .unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.already_applied, [1]);
+        assert_eq!(
+            report.applied.iter().map(|m| m.version).collect::<Vec<_>>(),
+            [2]
+        );
+        let sent = statements_sent(&mock);
+        assert_eq!(sent.len(), 2);
+        assert!(sent[0].starts_with("SHOW TABLES IN `main`.`ops` LIKE 'history'"));
+        assert!(sent[1].starts_with("SELECT version"));
+
+        let mock = MockTransport::new()
+            .on(
+                "POST",
+                STATEMENTS,
+                200,
+                result(&["tableName"], json!([["history"]])),
+            )
+            .on(
+                "POST",
+                STATEMENTS,
+                200,
+                history("CREATE TABLE a (id BIGINT);"),
+            );
+        let error = migrator(&mock)
+            .dry_run()
+            .migrate(&migrations)
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("was changed after it was applied"));
+    }
+
+    #[tokio::test]
+    async fn test_lock_contention() {
+        let mock = MockTransport::new()
+            .on("POST", STATEMENTS, 200, result(&[], json!([])))
+            .on("POST", STATEMENTS, 200, result(&[], json!([])))
+            .on("POST", STATEMENTS, 200, result(&[], json!([])))
+            .on(
+                "POST",
+                STATEMENTS,
+                200,
+                result(
+                    &["token", "owner", "acquired_at"],
+                    json!([["other", "someone@example.com", "2026-01-01 00:00:00"]]),
+                ),
+            );
+
+        let error = migrator(&mock)
+            .migrate(&[Migration::new(1, "one", "SELECT 1")])
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("locked by someone@example.com since 2026-01-01 00:00:00"));
+
+        // Nothing is applied, and a lock held by another run is left alone.
+        let sent = statements_sent(&mock);
+        assert_eq!(sent.len(), 4);
+        assert!(sent[2].starts_with("MERGE INTO `main`.`ops`.`history_lock`"));
+        assert!(!sent
+            .iter()
+            .any(|s| s.starts_with("INSERT") || s.starts_with("DELETE")));
+    }
+
+    #[tokio::test]
+    async fn test_lock_race_backs_out() {
+        let lock_rows = |token: &str| {
+            result(
+                &["token", "owner", "acquired_at"],
+                json!([
+                    ["other", "someone@example.com", "2026-01-01 00:00:00"],
+                    [token, "me@example.com", "2026-01-01 00:00:00"],
+                ]),
+            )
+        };
+        let mock = MockTransport::new()
+            .on("POST", STATEMENTS, 200, result(&[], json!([])))
+            .on("POST", STATEMENTS, 200, lock_rows("mine"))
+            .on("POST", STATEMENTS, 200, result(&[], json!([])));
+
+        let migrator = migrator(&mock);
+        let error = migrator.lock("mine").await.unwrap_err();
+        assert!(error.to_string().contains("at the same time"));
+
+        let sent = statements_sent(&mock);
+        assert_eq!(sent.len(), 3);
+        assert!(sent[0].starts_with("MERGE INTO"));
+        assert!(sent[1].starts_with("SELECT token"));
+        assert!(sent[2].starts_with(
+            "DELETE FROM `main`.`ops`.`history_lock` WHERE id = 1 AND token = :token"
+        ));
+    }
+}
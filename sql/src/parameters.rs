@@ -0,0 +1,135 @@
+use crate::types::Parameter;
+
+/// A Rust value that can be bound to a named statement parameter.
+///
+/// Values are sent as strings alongside their SQL type; `None` binds `NULL`.
+pub trait ParameterValue {
+    /// The SQL type name sent with the parameter, e.g. `BIGINT`.
+    fn sql_type() -> &'static str
+    where
+        Self: Sized;
+
+    /// The value rendered as a string, or `None` for `NULL`.
+    fn sql_value(&self) -> Option<String>;
+}
+
+macro_rules! impl_parameter_value {
+    ($($ty:ty => $sql:literal),* $(,)?) => {
+        $(
+            impl ParameterValue for $ty {
+                fn sql_type() -> &'static str {
+                    $sql
+                }
+
+                fn sql_value(&self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_parameter_value! {
+    bool => "BOOLEAN",
+    i8 => "TINYINT",
+    i16 => "SMALLINT",
+    i32 => "INT",
+    i64 => "BIGINT",
+    u8 => "SMALLINT",
+    u16 => "INT",
+    u32 => "BIGINT",
+    f32 => "FLOAT",
+    f64 => "DOUBLE",
+    String => "STRING",
+    &str => "STRING",
+}
+
+impl<T: ParameterValue> ParameterValue for Option<T> {
+    fn sql_type() -> &'static str {
+        T::sql_type()
+    }
+
+    fn sql_value(&self) -> Option<String> {
+        self.as_ref().and_then(T::sql_value)
+    }
+}
+
+impl Parameter {
+    /// A parameter typed from its Rust value.
+    pub fn new<V: ParameterValue>(name: impl Into<String>, value: V) -> Self {
+        Self {
+            name: name.into(),
+            value: value.sql_value(),
+            sql_type: Some(V::sql_type().to_string()),
+        }
+    }
+
+    /// A parameter with an explicit SQL type, for types without a Rust
+    /// mapping such as `DECIMAL(10,2)`, `DATE` or `TIMESTAMP`.
+    pub fn typed(
+        name: impl Into<String>,
+        value: Option<impl Into<String>>,
+        sql_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            value: value.map(Into::into),
+            sql_type: Some(sql_type.into()),
+        }
+    }
+}
+
+/// Quote a single identifier (catalog, schema, table or column name) with
+/// backticks, escaping embedded backticks.
+pub fn quote_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// Quote each part of a multi-part name and join them with dots.
+pub fn quote_name(parts: &[&str]) -> String {
+    parts
+        .iter()
+        .map(|p| quote_identifier(p))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Quote a string literal with single quotes, escaping backslashes and quotes.
+///
+/// Prefer [`Request::bind`](crate::Request::bind) for values; this is for
+/// places that do not accept parameter markers, such as `COPY INTO` paths.
+pub fn quote_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Request;
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("sales"), "`sales`");
+        assert_eq!(
+            quote_name(&["main", "a`; DROP TABLE x; --"]),
+            "`main`.`a``; DROP TABLE x; --`"
+        );
+        assert_eq!(quote_string(r"it's C:\tmp"), r"'it\'s C:\\tmp'");
+    }
+
+    #[test]
+    fn test_bind_serializes_parameters() {
+        let request = Request::new("SELECT * FROM t WHERE id = :id AND name = :name", "wh")
+            .bind("id", 42i64)
+            .bind("name", None::<&str>);
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["parameters"],
+            serde_json::json!([
+                {"name": "id", "value": "42", "type": "BIGINT"},
+                {"name": "name", "type": "STRING"},
+            ])
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::query_types::{CreateQueryRequest, ListQueryObjectsResponse, Query, UpdateQueryRequest};
+use databricks_core::paginator::{Page, Paginator};
 use databricks_core::{Client, Error};
 
 const PATH: &str = "/api/2.0/sql/queries";
@@ -40,6 +41,17 @@ impl Queries {
         self.client.get_with_query(PATH, &pairs).await
     }
 
+    /// Iterate all queries, fetching pages on demand.
+    pub fn list_all(&self) -> Paginator<Query> {
+        Paginator::new(self.client.clone(), |client, token| async move {
+            let response = Self::new(client).list(None, token.as_deref()).await?;
+            Ok(Page {
+                items: response.results,
+                next_token: response.next_page_token,
+            })
+        })
+    }
+
     /// Update a query.
     pub async fn update(&self, id: &str, request: &UpdateQueryRequest) -> Result<Query, Error> {
         let path = format!("{}/{}", PATH, id);
@@ -1,8 +1,16 @@
-use crate::query_history_types::ListQueriesResponse;
+use crate::query_history_types::{
+    GroupBy, ListQueriesResponse, QueryFilter, QueryInfo, QueryStats, QueryStatus,
+};
+use databricks_core::paginator::{Page, Paginator};
 use databricks_core::{Client, Error};
+use futures::stream::{self, Stream};
+use std::collections::BTreeMap;
 
 const PATH: &str = "/api/2.0/sql/history/queries";
 
+/// Page size used when iterating all of history.
+const PAGE_SIZE: i32 = 1000;
+
 pub struct QueryHistory {
     client: Client,
 }
@@ -19,17 +27,315 @@ impl QueryHistory {
         page_token: Option<&str>,
         include_metrics: Option<bool>,
     ) -> Result<ListQueriesResponse, Error> {
-        let mut query: Vec<(&str, String)> = Vec::new();
-        if let Some(mr) = max_results {
-            query.push(("max_results", mr.to_string()));
-        }
-        if let Some(pt) = page_token {
-            query.push(("page_token", pt.to_string()));
-        }
-        if let Some(im) = include_metrics {
-            query.push(("include_metrics", im.to_string()));
+        list(&self.client, None, max_results, page_token, include_metrics).await
+    }
+
+    /// List one page of query history matching `filter`.
+    pub async fn list_filtered(
+        &self,
+        filter: &QueryFilter,
+        max_results: Option<i32>,
+        page_token: Option<&str>,
+        include_metrics: Option<bool>,
+    ) -> Result<ListQueriesResponse, Error> {
+        list(
+            &self.client,
+            Some(filter),
+            max_results,
+            page_token,
+            include_metrics,
+        )
+        .await
+    }
+
+    /// Iterate all queries matching `filter`, fetching pages on demand.
+    pub fn list_all(&self, filter: QueryFilter, include_metrics: bool) -> Paginator<QueryInfo> {
+        Paginator::new(self.client.clone(), move |client, token| {
+            let filter = filter.clone();
+            async move {
+                let response = list(
+                    &client,
+                    Some(&filter),
+                    Some(PAGE_SIZE),
+                    token.as_deref(),
+                    Some(include_metrics),
+                )
+                .await?;
+                let next_token = match response.has_next_page {
+                    Some(false) => None,
+                    _ => response.next_page_token,
+                };
+                Ok(Page {
+                    items: response.res,
+                    next_token,
+                })
+            }
+        })
+    }
+
+    /// Stream all queries matching `filter`.
+    pub fn stream(
+        &self,
+        filter: QueryFilter,
+        include_metrics: bool,
+    ) -> impl Stream<Item = Result<QueryInfo, Error>> {
+        stream::unfold(
+            self.list_all(filter, include_metrics),
+            |mut pages| async move { pages.next().await.map(|item| (item, pages)) },
+        )
+    }
+
+    /// Fetch all queries matching `filter`, with metrics, and aggregate them.
+    pub async fn analyze(
+        &self,
+        filter: QueryFilter,
+        group_by: GroupBy,
+    ) -> Result<Vec<QueryStats>, Error> {
+        let queries = self.list_all(filter, true).collect_all().await?;
+        Ok(Self::summarize(&queries, group_by))
+    }
+
+    /// Aggregate query durations, bytes read, spill and result cache hits
+    /// per group, ordered by key.
+    ///
+    /// Durations use the nearest-rank percentile over queries that report one.
+    pub fn summarize(queries: &[QueryInfo], group_by: GroupBy) -> Vec<QueryStats> {
+        let mut groups: BTreeMap<String, Vec<&QueryInfo>> = BTreeMap::new();
+        for query in queries {
+            let key = match group_by {
+                GroupBy::Warehouse => query.warehouse_id.clone(),
+                GroupBy::User => query.user_name.clone(),
+                GroupBy::StatementType => query.statement_type.map(|t| t.as_str().to_string()),
+            };
+            groups
+                .entry(key.unwrap_or_else(|| "unknown".into()))
+                .or_default()
+                .push(query);
         }
-        let pairs: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
-        self.client.get_with_query(PATH, &pairs).await
+
+        groups
+            .into_iter()
+            .map(|(key, queries)| stats(key, &queries))
+            .collect()
+    }
+}
+
+fn stats(key: String, queries: &[&QueryInfo]) -> QueryStats {
+    let mut durations: Vec<i64> = queries
+        .iter()
+        .filter_map(|q| {
+            q.duration
+                .or_else(|| q.metrics.as_ref().and_then(|m| m.total_time_ms))
+        })
+        .collect();
+    durations.sort_unstable();
+
+    let metric = |f: fn(&crate::QueryMetrics) -> Option<i64>| -> i64 {
+        queries
+            .iter()
+            .filter_map(|q| q.metrics.as_ref().and_then(f))
+            .sum()
+    };
+    let cache_hits = queries
+        .iter()
+        .filter(|q| {
+            q.metrics
+                .as_ref()
+                .and_then(|m| m.result_from_cache)
+                .unwrap_or(false)
+        })
+        .count();
+
+    QueryStats {
+        key,
+        query_count: queries.len() as u64,
+        failed_count: queries
+            .iter()
+            .filter(|q| q.status == Some(QueryStatus::Failed))
+            .count() as u64,
+        p50_duration_ms: percentile(&durations, 50),
+        p95_duration_ms: percentile(&durations, 95),
+        total_duration_ms: durations.iter().sum(),
+        total_task_time_ms: metric(|m| m.task_total_time_ms),
+        read_bytes: metric(|m| m.read_bytes),
+        spill_to_disk_bytes: metric(|m| m.spill_to_disk_bytes),
+        cache_hit_rate: if queries.is_empty() {
+            0.0
+        } else {
+            cache_hits as f64 / queries.len() as f64
+        },
+    }
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[i64], p: usize) -> Option<i64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    Some(sorted[rank - 1])
+}
+
+async fn list(
+    client: &Client,
+    filter: Option<&QueryFilter>,
+    max_results: Option<i32>,
+    page_token: Option<&str>,
+    include_metrics: Option<bool>,
+) -> Result<ListQueriesResponse, Error> {
+    let mut query: Vec<(&str, String)> = Vec::new();
+    if let Some(mr) = max_results {
+        query.push(("max_results", mr.to_string()));
+    }
+    if let Some(pt) = page_token {
+        query.push(("page_token", pt.to_string()));
+    }
+    if let Some(im) = include_metrics {
+        query.push(("include_metrics", im.to_string()));
+    }
+    if let Some(filter) = filter {
+        query.extend(filter.to_query());
+    }
+    let pairs: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    client.get_with_query(PATH, &pairs).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query_history_types::{QueryMetrics, QueryStatementType};
+
+    fn query(warehouse: &str, duration: i64, from_cache: bool, status: QueryStatus) -> QueryInfo {
+        let mut info: QueryInfo = serde_json::from_value(serde_json::json!({
+            "warehouse_id": warehouse,
+            "duration": duration,
+        }))
+        .unwrap();
+        info.status = Some(status);
+        info.metrics = Some(QueryMetrics {
+            compilation_time_ms: None,
+            execution_time_ms: None,
+            read_bytes: Some(100),
+            rows_produced_count: None,
+            rows_read_count: None,
+            result_from_cache: Some(from_cache),
+            total_time_ms: None,
+            result_fetch_time_ms: None,
+            network_sent_bytes: None,
+            spill_to_disk_bytes: Some(10),
+            task_total_time_ms: None,
+        });
+        info
+    }
+
+    #[test]
+    fn test_summarize_by_warehouse() {
+        let mut queries: Vec<QueryInfo> = (1..=20)
+            .map(|i| query("a", i * 10, i % 4 == 0, QueryStatus::Finished))
+            .collect();
+        queries.push(query("b", 5, false, QueryStatus::Failed));
+
+        let stats = QueryHistory::summarize(&queries, GroupBy::Warehouse);
+        assert_eq!(stats.len(), 2);
+
+        let a = &stats[0];
+        assert_eq!(a.key, "a");
+        assert_eq!(a.query_count, 20);
+        assert_eq!(a.p50_duration_ms, Some(100));
+        assert_eq!(a.p95_duration_ms, Some(190));
+        assert_eq!(a.read_bytes, 2000);
+        assert_eq!(a.spill_to_disk_bytes, 200);
+        assert_eq!(a.cache_hit_rate, 0.25);
+
+        assert_eq!(stats[1].failed_count, 1);
+        assert_eq!(stats[1].p95_duration_ms, Some(5));
+    }
+
+    #[test]
+    fn test_summarize_by_statement_type_uses_api_names() {
+        let mut select = query("a", 10, false, QueryStatus::Finished);
+        select.statement_type = Some(QueryStatementType::Select);
+        let mut insert = query("a", 20, false, QueryStatus::Finished);
+        insert.statement_type = Some(QueryStatementType::Insert);
+
+        let stats = QueryHistory::summarize(&[select, insert], GroupBy::StatementType);
+        let keys: Vec<&str> = stats.iter().map(|s| s.key.as_str()).collect();
+        assert_eq!(keys, ["INSERT", "SELECT"]);
+    }
+
+    #[test]
+    fn test_unknown_statement_types_and_statuses_deserialize() {
+        let page: ListQueriesResponse = serde_json::from_value(serde_json::json!({
+            "res": [
+                {"query_id": "q1", "statement_type": "SHOW", "status": "STARTED"},
+                {"query_id": "q2", "statement_type": "VACUUM_SOMETHING_NEW", "status": "PAUSED"},
+            ],
+        }))
+        .unwrap();
+
+        assert_eq!(page.res[0].statement_type, Some(QueryStatementType::Show));
+        assert_eq!(page.res[0].status, Some(QueryStatus::Started));
+        assert_eq!(page.res[1].statement_type, Some(QueryStatementType::Other));
+        assert_eq!(page.res[1].status, Some(QueryStatus::Unknown));
+    }
+
+    #[test]
+    fn test_filter_query_parameters() {
+        let filter = QueryFilter::default()
+            .start_time_range(1000, None)
+            .status(QueryStatus::Failed)
+            .warehouse_id("wh1")
+            .warehouse_id("wh2");
+        assert_eq!(
+            filter.to_query(),
+            vec![
+                (
+                    "filter_by.query_start_time_range.start_time_ms",
+                    "1000".to_string()
+                ),
+                ("filter_by.statuses", "FAILED".to_string()),
+                ("filter_by.warehouse_ids", "wh1".to_string()),
+                ("filter_by.warehouse_ids", "wh2".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_all_encodes_page_token() {
+        use databricks_core::transport::mock::MockTransport;
+
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                PATH,
+                200,
+                serde_json::json!({
+                    "res": [{"query_id": "q1"}],
+                    "next_page_token": "a+b/c==",
+                    "has_next_page": true,
+                }),
+            )
+            .on(
+                "GET",
+                PATH,
+                200,
+                serde_json::json!({"res": [{"query_id": "q2"}], "has_next_page": false}),
+            );
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+
+        let queries = QueryHistory::new(client)
+            .list_all(QueryFilter::default(), false)
+            .collect_all()
+            .await
+            .unwrap();
+        assert_eq!(queries.len(), 2);
+        assert!(mock.requests()[1]
+            .path
+            .contains("page_token=a%2Bb%2Fc%3D%3D"));
     }
 }
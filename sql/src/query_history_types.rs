@@ -50,27 +50,92 @@ pub struct QueryInfo {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[non_exhaustive]
 pub enum QueryStatus {
     Queued,
+    Started,
     Running,
     Canceled,
     Failed,
     Finished,
+    /// A status this version does not know about.
+    #[serde(other)]
+    Unknown,
+}
+
+impl QueryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueryStatus::Queued => "QUEUED",
+            QueryStatus::Started => "STARTED",
+            QueryStatus::Running => "RUNNING",
+            QueryStatus::Canceled => "CANCELED",
+            QueryStatus::Failed => "FAILED",
+            QueryStatus::Finished => "FINISHED",
+            QueryStatus::Unknown => "UNKNOWN",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[non_exhaustive]
 pub enum QueryStatementType {
     Select,
     Insert,
     Update,
     Delete,
+    Merge,
+    Copy,
     Create,
     Drop,
     Alter,
+    Truncate,
+    Replace,
+    Refresh,
+    Optimize,
+    Analyze,
+    Describe,
+    Explain,
+    Show,
+    Use,
+    Set,
+    Grant,
+    Revoke,
+    /// Any statement type without its own variant.
+    #[serde(other)]
     Other,
 }
 
+impl QueryStatementType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueryStatementType::Select => "SELECT",
+            QueryStatementType::Insert => "INSERT",
+            QueryStatementType::Update => "UPDATE",
+            QueryStatementType::Delete => "DELETE",
+            QueryStatementType::Merge => "MERGE",
+            QueryStatementType::Copy => "COPY",
+            QueryStatementType::Create => "CREATE",
+            QueryStatementType::Drop => "DROP",
+            QueryStatementType::Alter => "ALTER",
+            QueryStatementType::Truncate => "TRUNCATE",
+            QueryStatementType::Replace => "REPLACE",
+            QueryStatementType::Refresh => "REFRESH",
+            QueryStatementType::Optimize => "OPTIMIZE",
+            QueryStatementType::Analyze => "ANALYZE",
+            QueryStatementType::Describe => "DESCRIBE",
+            QueryStatementType::Explain => "EXPLAIN",
+            QueryStatementType::Show => "SHOW",
+            QueryStatementType::Use => "USE",
+            QueryStatementType::Set => "SET",
+            QueryStatementType::Grant => "GRANT",
+            QueryStatementType::Revoke => "REVOKE",
+            QueryStatementType::Other => "OTHER",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryMetrics {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -97,7 +162,7 @@ pub struct QueryMetrics {
     pub task_total_time_ms: Option<i64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct QueryFilter {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub query_start_time_range: Option<TimeRange>,
@@ -111,7 +176,75 @@ pub struct QueryFilter {
     pub statement_ids: Option<Vec<String>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl QueryFilter {
+    /// Only queries started in `[start_time_ms, end_time_ms)`, in epoch
+    /// milliseconds.
+    pub fn start_time_range(mut self, start_time_ms: i64, end_time_ms: Option<i64>) -> Self {
+        self.query_start_time_range = Some(TimeRange {
+            start_time_ms: Some(start_time_ms),
+            end_time_ms,
+        });
+        self
+    }
+
+    pub fn status(mut self, status: QueryStatus) -> Self {
+        self.statuses.get_or_insert_with(Vec::new).push(status);
+        self
+    }
+
+    pub fn user_id(mut self, user_id: i64) -> Self {
+        self.user_ids.get_or_insert_with(Vec::new).push(user_id);
+        self
+    }
+
+    pub fn warehouse_id(mut self, warehouse_id: impl Into<String>) -> Self {
+        self.warehouse_ids
+            .get_or_insert_with(Vec::new)
+            .push(warehouse_id.into());
+        self
+    }
+
+    pub fn statement_id(mut self, statement_id: impl Into<String>) -> Self {
+        self.statement_ids
+            .get_or_insert_with(Vec::new)
+            .push(statement_id.into());
+        self
+    }
+
+    /// Encode as `filter_by.*` query parameters.
+    pub(crate) fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(range) = &self.query_start_time_range {
+            if let Some(start) = range.start_time_ms {
+                query.push((
+                    "filter_by.query_start_time_range.start_time_ms",
+                    start.to_string(),
+                ));
+            }
+            if let Some(end) = range.end_time_ms {
+                query.push((
+                    "filter_by.query_start_time_range.end_time_ms",
+                    end.to_string(),
+                ));
+            }
+        }
+        for status in self.statuses.iter().flatten() {
+            query.push(("filter_by.statuses", status.as_str().to_string()));
+        }
+        for user_id in self.user_ids.iter().flatten() {
+            query.push(("filter_by.user_ids", user_id.to_string()));
+        }
+        for warehouse_id in self.warehouse_ids.iter().flatten() {
+            query.push(("filter_by.warehouse_ids", warehouse_id.clone()));
+        }
+        for statement_id in self.statement_ids.iter().flatten() {
+            query.push(("filter_by.statement_ids", statement_id.clone()));
+        }
+        query
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TimeRange {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub start_time_ms: Option<i64>,
@@ -132,3 +265,32 @@ pub struct ListQueriesResponse {
     #[serde(default)]
     pub has_next_page: Option<bool>,
 }
+
+// ============================================================================
+// Analytics types
+// ============================================================================
+
+/// Dimension to aggregate query history by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    Warehouse,
+    User,
+    StatementType,
+}
+
+/// Aggregated performance of a group of queries.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryStats {
+    /// Warehouse id, user name or statement type, depending on [`GroupBy`].
+    pub key: String,
+    pub query_count: u64,
+    pub failed_count: u64,
+    pub p50_duration_ms: Option<i64>,
+    pub p95_duration_ms: Option<i64>,
+    pub total_duration_ms: i64,
+    pub total_task_time_ms: i64,
+    pub read_bytes: i64,
+    pub spill_to_disk_bytes: i64,
+    /// Share of queries answered from the result cache, from 0 to 1.
+    pub cache_hit_rate: f64,
+}
@@ -0,0 +1,421 @@
+use crate::query_history::QueryHistory;
+use crate::query_history_types::{QueryFilter, QueryInfo, QueryStatus};
+use databricks_core::runtime::{SystemTime, UNIX_EPOCH};
+use databricks_core::{Client, Error};
+use futures::stream::{self, Stream};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+const DEFAULT_LOOKBACK: Duration = Duration::from_secs(5 * 60);
+
+/// A change in a query observed by [`QueryWatcher`].
+#[derive(Debug, Clone)]
+pub enum QueryEvent {
+    /// The query was queued or started running.
+    Started(QueryInfo),
+    /// The query's status or metrics changed while it was running.
+    Progressed(QueryInfo),
+    Finished(QueryInfo),
+    Failed(QueryInfo),
+    Canceled(QueryInfo),
+}
+
+impl QueryEvent {
+    pub fn query(&self) -> &QueryInfo {
+        match self {
+            QueryEvent::Started(q)
+            | QueryEvent::Progressed(q)
+            | QueryEvent::Finished(q)
+            | QueryEvent::Failed(q)
+            | QueryEvent::Canceled(q) => q,
+        }
+    }
+
+    /// Whether no further events follow for this query.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            QueryEvent::Finished(_) | QueryEvent::Failed(_) | QueryEvent::Canceled(_)
+        )
+    }
+}
+
+/// Tails query history, emitting an event each time a query starts, changes
+/// or ends.
+///
+/// Each poll reads the queries that started since the newest one already
+/// seen, re-reads the queries still running by id, and compares every
+/// query's `lookup_key` and status with the last poll, so each change is
+/// reported once. The interval doubles, up to
+/// [`QueryWatcher::max_backoff`], while nothing is running or requests fail,
+/// and resets as soon as there is activity again.
+///
+/// Query ids are statement ids, so long runners can be stopped with
+/// [`Statements::cancel`](crate::Statements::cancel):
+///
+/// ```no_run
+/// # async fn run(client: databricks_core::Client) -> Result<(), databricks_core::Error> {
+/// use databricks_sql::{QueryEvent, QueryWatcher, Statements};
+/// use futures::{pin_mut, TryStreamExt};
+///
+/// let statements = Statements::new(client.clone());
+/// let events = QueryWatcher::new(client).warehouse_id("wh-id").stream();
+/// pin_mut!(events);
+/// while let Some(event) = events.try_next().await? {
+///     let query = event.query();
+///     if let (QueryEvent::Progressed(_), Some(id)) = (&event, &query.query_id) {
+///         if query.duration.unwrap_or(0) > 10 * 60 * 1000 {
+///             statements.cancel(id).await?;
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct QueryWatcher {
+    client: Client,
+    history: QueryHistory,
+    filter: QueryFilter,
+    poll_interval: Duration,
+    max_backoff: Duration,
+    lookback: Duration,
+}
+
+impl QueryWatcher {
+    pub fn new(client: Client) -> Self {
+        Self {
+            history: QueryHistory::new(client.clone()),
+            client,
+            filter: QueryFilter::default(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            lookback: DEFAULT_LOOKBACK,
+        }
+    }
+
+    /// Only watch queries on this warehouse. May be called more than once.
+    pub fn warehouse_id(mut self, warehouse_id: impl Into<String>) -> Self {
+        self.filter = self.filter.warehouse_id(warehouse_id);
+        self
+    }
+
+    /// Only watch queries run by this user. May be called more than once.
+    pub fn user_id(mut self, user_id: i64) -> Self {
+        self.filter = self.filter.user_id(user_id);
+        self
+    }
+
+    /// Interval between polls while queries are running (default 5s).
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Longest interval between polls when idle or failing (default 60s).
+    pub fn max_backoff(mut self, max: Duration) -> Self {
+        self.max_backoff = max;
+        self
+    }
+
+    /// How far back the first poll looks for queries that are still running
+    /// (default 5 minutes).
+    pub fn lookback(mut self, lookback: Duration) -> Self {
+        self.lookback = lookback;
+        self
+    }
+
+    /// Poll indefinitely, yielding events as they are observed.
+    ///
+    /// A failed poll yields its error and is retried after the backoff; stop
+    /// consuming the stream to stop watching.
+    pub fn stream(self) -> impl Stream<Item = Result<QueryEvent, Error>> {
+        let started_ms = now_ms();
+        let state = Watch {
+            tracker: Tracker::new(started_ms),
+            high_water_ms: started_ms - self.lookback.as_millis() as i64,
+            delay: Duration::ZERO,
+            pending: VecDeque::new(),
+            watcher: self,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(event) = state.pending.pop_front() {
+                    return Some((Ok(event), state));
+                }
+                if !state.delay.is_zero() {
+                    state.watcher.client.runtime().sleep(state.delay).await;
+                }
+                if let Err(e) = state.poll().await {
+                    state.delay = state.backoff();
+                    return Some((Err(e), state));
+                }
+            }
+        })
+    }
+}
+
+struct Watch {
+    watcher: QueryWatcher,
+    tracker: Tracker,
+    /// Start time of the newest query seen; later polls only list queries
+    /// that started since.
+    high_water_ms: i64,
+    delay: Duration,
+    pending: VecDeque<QueryEvent>,
+}
+
+impl Watch {
+    async fn poll(&mut self) -> Result<(), Error> {
+        let filter = self
+            .watcher
+            .filter
+            .clone()
+            .start_time_range(self.high_water_ms, None);
+        let mut queries = self
+            .watcher
+            .history
+            .list_all(filter, true)
+            .collect_all()
+            .await?;
+        if let Some(newest) = queries.iter().filter_map(|q| q.query_start_time_ms).max() {
+            self.high_water_ms = self.high_water_ms.max(newest);
+        }
+
+        // Queries that started before the window are only polled while
+        // they are still running.
+        let listed: HashSet<&str> = queries
+            .iter()
+            .filter_map(|q| q.query_id.as_deref())
+            .collect();
+        let mut running = self
+            .tracker
+            .running
+            .keys()
+            .filter(|id| !listed.contains(id.as_str()))
+            .peekable();
+        if running.peek().is_some() {
+            let filter = running.fold(self.watcher.filter.clone(), |f, id| f.statement_id(id));
+            queries.extend(
+                self.watcher
+                    .history
+                    .list_all(filter, true)
+                    .collect_all()
+                    .await?,
+            );
+        }
+
+        self.pending.extend(self.tracker.update(queries));
+        let high_water_ms = self.high_water_ms;
+        self.tracker
+            .ended
+            .retain(|_, start_ms| *start_ms >= high_water_ms);
+
+        self.delay = if self.pending.is_empty() && self.tracker.running.is_empty() {
+            self.backoff()
+        } else {
+            self.watcher.poll_interval
+        };
+        Ok(())
+    }
+
+    fn backoff(&self) -> Duration {
+        (self.delay * 2)
+            .max(self.watcher.poll_interval)
+            .min(self.watcher.max_backoff)
+    }
+}
+
+/// Last observed state of each query, used to turn snapshots into events.
+struct Tracker {
+    started_ms: i64,
+    /// Unfinished queries by id, with their change marker.
+    running: HashMap<String, String>,
+    /// Ended queries by id, with their start time, until they fall behind
+    /// the high-water mark.
+    ended: HashMap<String, i64>,
+}
+
+impl Tracker {
+    fn new(started_ms: i64) -> Self {
+        Self {
+            started_ms,
+            running: HashMap::new(),
+            ended: HashMap::new(),
+        }
+    }
+
+    fn update(&mut self, mut queries: Vec<QueryInfo>) -> Vec<QueryEvent> {
+        queries.sort_by_key(|q| q.query_start_time_ms);
+        let mut events = Vec::new();
+
+        for query in queries {
+            let Some(id) = query.query_id.clone() else {
+                continue;
+            };
+            if self.ended.contains_key(&id) {
+                continue;
+            }
+            let start_ms = query.query_start_time_ms.unwrap_or(self.started_ms);
+
+            if is_final(&query) {
+                let previous = self.running.remove(&id);
+                // Skip queries that had already ended before watching began.
+                let ended_ms = query.query_end_time_ms.unwrap_or(i64::MAX);
+                if previous.is_some() || ended_ms >= self.started_ms {
+                    events.push(match query.status {
+                        Some(QueryStatus::Failed) => QueryEvent::Failed(query),
+                        Some(QueryStatus::Canceled) => QueryEvent::Canceled(query),
+                        _ => QueryEvent::Finished(query),
+                    });
+                }
+                self.ended.insert(id, start_ms);
+                continue;
+            }
+
+            let marker = change_marker(&query);
+            match self.running.insert(id, marker.clone()) {
+                None => events.push(QueryEvent::Started(query)),
+                Some(previous) if previous != marker => events.push(QueryEvent::Progressed(query)),
+                Some(_) => {}
+            }
+        }
+
+        events
+    }
+}
+
+fn is_final(query: &QueryInfo) -> bool {
+    query.is_final == Some(true)
+        || matches!(
+            query.status,
+            Some(QueryStatus::Finished | QueryStatus::Failed | QueryStatus::Canceled)
+        )
+}
+
+/// Identifies a version of a running query. The service changes
+/// `lookup_key` whenever the entry is updated.
+fn change_marker(query: &QueryInfo) -> String {
+    let status = query.status.map(|s| s.as_str()).unwrap_or("");
+    format!("{}:{}", status, query.lookup_key.as_deref().unwrap_or(""))
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use databricks_core::transport::mock::MockTransport;
+    use serde_json::json;
+
+    fn query(id: &str, status: &str, lookup_key: &str, start_ms: i64, end_ms: i64) -> QueryInfo {
+        serde_json::from_value(json!({
+            "query_id": id,
+            "status": status,
+            "lookup_key": lookup_key,
+            "query_start_time_ms": start_ms,
+            "query_end_time_ms": end_ms,
+            "is_final": matches!(status, "FINISHED" | "FAILED" | "CANCELED"),
+        }))
+        .unwrap()
+    }
+
+    fn kinds(events: &[QueryEvent]) -> Vec<&'static str> {
+        events
+            .iter()
+            .map(|e| match e {
+                QueryEvent::Started(_) => "started",
+                QueryEvent::Progressed(_) => "progressed",
+                QueryEvent::Finished(_) => "finished",
+                QueryEvent::Failed(_) => "failed",
+                QueryEvent::Canceled(_) => "canceled",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tracker_deduplicates_snapshots() {
+        let mut tracker = Tracker::new(1500);
+
+        // `done` ended after watching began and is reported; `old` ended
+        // before and is skipped.
+        let events = tracker.update(vec![
+            query("a", "RUNNING", "k1", 1000, 0),
+            query("done", "FINISHED", "k1", 1000, 2000),
+            query("old", "FINISHED", "k1", 500, 1000),
+        ]);
+        assert_eq!(kinds(&events), ["started", "finished"]);
+
+        assert!(tracker
+            .update(vec![query("a", "RUNNING", "k1", 1000, 0)])
+            .is_empty());
+
+        let events = tracker.update(vec![query("a", "RUNNING", "k2", 1000, 0)]);
+        assert_eq!(kinds(&events), ["progressed"]);
+
+        let events = tracker.update(vec![query("a", "FAILED", "k3", 1000, 2000)]);
+        assert_eq!(kinds(&events), ["failed"]);
+        assert!(tracker
+            .update(vec![query("a", "FAILED", "k3", 1000, 2000)])
+            .is_empty());
+        assert!(tracker.running.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_poll_repolls_running_queries_by_id() {
+        let path = "/api/2.0/sql/history/queries";
+        let now = now_ms();
+        let mock = MockTransport::new()
+            .on(
+                "GET",
+                path,
+                200,
+                json!({"res": [query("a", "RUNNING", "k1", now - 1000, 0), query("b", "RUNNING", "k1", now, 0)]}),
+            )
+            .on("GET", path, 200, json!({"res": [query("b", "FINISHED", "k2", now, now + 1)]}))
+            .on("GET", path, 200, json!({"res": [query("a", "RUNNING", "k2", now - 1000, 0)]}));
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        let mut watch = Watch {
+            tracker: Tracker::new(now - 5000),
+            high_water_ms: now - 5000,
+            delay: Duration::ZERO,
+            pending: VecDeque::new(),
+            watcher: QueryWatcher::new(client),
+        };
+
+        watch.poll().await.unwrap();
+        assert_eq!(
+            kinds(&watch.pending.drain(..).collect::<Vec<_>>()),
+            ["started", "started"]
+        );
+        assert_eq!(watch.high_water_ms, now);
+
+        watch.poll().await.unwrap();
+        assert_eq!(
+            kinds(&watch.pending.drain(..).collect::<Vec<_>>()),
+            ["progressed", "finished"]
+        );
+
+        // The second poll lists from the high-water mark, then asks for the
+        // one running query it did not see by id.
+        let requests: Vec<_> = mock.requests().into_iter().map(|r| r.path).collect();
+        let start = format!("filter_by.query_start_time_range.start_time_ms={}", now);
+        assert!(requests[1].contains(&start));
+        assert!(!requests[1].contains("statement_ids"));
+        assert!(requests[2].contains("filter_by.statement_ids=a"));
+        assert!(!requests[2].contains("start_time_ms"));
+        assert_eq!(requests.len(), 3);
+    }
+}
@@ -1,4 +1,8 @@
-use crate::types::{CreateVisualizationRequest, UpdateVisualizationRequest, Visualization};
+use crate::types::{
+    CreateVisualizationRequest, ListVisualizationsResponse, UpdateVisualizationRequest,
+    Visualization,
+};
+use databricks_core::paginator::{Page, Paginator};
 use databricks_core::{Client, Error};
 
 const PATH: &str = "/api/2.0/sql/visualizations";
@@ -20,6 +24,42 @@ impl QueryVisualizations {
         self.client.post(PATH, request).await
     }
 
+    /// List the visualizations of a query.
+    pub async fn list(
+        &self,
+        query_id: &str,
+        page_size: Option<i32>,
+        page_token: Option<&str>,
+    ) -> Result<ListVisualizationsResponse, Error> {
+        let path = format!("/api/2.0/sql/queries/{}/visualizations", query_id);
+        let mut query: Vec<(&str, String)> = Vec::new();
+        if let Some(ps) = page_size {
+            query.push(("page_size", ps.to_string()));
+        }
+        if let Some(pt) = page_token {
+            query.push(("page_token", pt.to_string()));
+        }
+        let pairs: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        self.client.get_with_query(&path, &pairs).await
+    }
+
+    /// Iterate all visualizations of a query, fetching pages on demand.
+    pub fn list_all(&self, query_id: &str) -> Paginator<Visualization> {
+        let query_id = query_id.to_string();
+        Paginator::new(self.client.clone(), move |client, token| {
+            let query_id = query_id.clone();
+            async move {
+                let response = Self::new(client)
+                    .list(&query_id, None, token.as_deref())
+                    .await?;
+                Ok(Page {
+                    items: response.results,
+                    next_token: response.next_page_token,
+                })
+            }
+        })
+    }
+
     /// Delete a visualization by ID.
     pub async fn delete(&self, id: &str) -> Result<(), Error> {
         let path = format!("{}/{}", PATH, id);
@@ -0,0 +1,310 @@
+use crate::external_links::Downloader;
+use crate::rows::{FromRow, Row};
+use crate::types::{Column, ExternalLink, Format, Response, ResultData};
+use crate::Statements;
+use databricks_core::{Client, Error};
+use futures::stream::{self, BoxStream, Stream, StreamExt, TryStreamExt};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+const DEFAULT_PREFETCH: usize = 2;
+
+type Rows = Vec<Vec<Option<String>>>;
+
+/// Rows of a succeeded statement across all of its result chunks.
+///
+/// Chunk 0 comes from the response itself; later chunks are fetched on
+/// demand with up to `prefetch` chunks requested ahead of the consumer.
+/// Handles both `INLINE` and `EXTERNAL_LINKS` dispositions for the
+/// `JSON_ARRAY` format; use [`crate::ArrowReader`] for Arrow results.
+///
+/// ```no_run
+/// # async fn run(statements: databricks_sql::Statements, response: databricks_sql::Response) -> Result<(), databricks_core::Error> {
+/// use futures::TryStreamExt;
+///
+/// let stream = statements.result_stream(&response).prefetch(4);
+/// if stream.truncated() {
+///     eprintln!("result was truncated by the row limit");
+/// }
+/// let rows: Vec<(String, i64)> = stream.rows().try_collect().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ResultStream {
+    source: Arc<Source>,
+    columns: Vec<Column>,
+    total_row_count: Option<i64>,
+    total_chunk_count: i32,
+    truncated: bool,
+    prefetch: usize,
+    chunks: Option<BoxStream<'static, Result<Rows, Error>>>,
+    buffer: std::vec::IntoIter<Vec<Option<String>>>,
+}
+
+impl ResultStream {
+    pub fn new(client: Client, response: &Response) -> Self {
+        let manifest = response.manifest.as_ref();
+        let first = response.result.clone();
+        let links = first
+            .iter()
+            .flat_map(|r| r.external_links.iter())
+            .map(|l| (l.chunk_index, l.clone()))
+            .collect();
+
+        Self {
+            source: Arc::new(Source {
+                statements: Statements::new(client.clone()),
+                downloader: Downloader::new(client),
+                statement_id: response.statement_id.clone(),
+                format: manifest.and_then(|m| m.format),
+                first,
+                links,
+            }),
+            columns: response.columns().to_vec(),
+            total_row_count: manifest.and_then(|m| m.total_row_count),
+            total_chunk_count: manifest
+                .and_then(|m| m.total_chunk_count)
+                .unwrap_or(i32::from(response.result.is_some())),
+            truncated: manifest.is_some_and(|m| m.truncated),
+            prefetch: DEFAULT_PREFETCH,
+            chunks: None,
+            buffer: Vec::new().into_iter(),
+        }
+    }
+
+    /// Number of chunks fetched ahead of the consumer (default 2).
+    pub fn prefetch(mut self, chunks: usize) -> Self {
+        self.prefetch = chunks.max(1);
+        self
+    }
+
+    /// Use a configured downloader for external-link chunks.
+    pub fn downloader(mut self, downloader: Downloader) -> Self {
+        if let Some(source) = Arc::get_mut(&mut self.source) {
+            source.downloader = downloader;
+        }
+        self
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Total rows across all chunks, as reported by the manifest.
+    pub fn total_row_count(&self) -> Option<i64> {
+        self.total_row_count
+    }
+
+    pub fn total_chunk_count(&self) -> i32 {
+        self.total_chunk_count
+    }
+
+    /// Whether the result was cut short by the statement's `row_limit` or
+    /// the API's result size limit.
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Decode each row into `T`.
+    pub fn rows<T: FromRow>(self) -> impl Stream<Item = Result<T, Error>> {
+        let columns = self.columns.clone();
+        self.map(move |row| row.and_then(|values| T::from_row(&Row::new(&columns, &values))))
+    }
+
+    /// Read every remaining row into memory.
+    pub async fn collect_all(self) -> Result<Rows, Error> {
+        self.try_collect().await
+    }
+
+    fn start(&self) -> BoxStream<'static, Result<Rows, Error>> {
+        let source = self.source.clone();
+        if source.format.is_some_and(|f| f != Format::JsonArray) {
+            let error = Error::Other(format!(
+                "ResultStream reads JSON_ARRAY results, statement used {:?}",
+                source.format
+            ));
+            return stream::once(async move { Err(error) }).boxed();
+        }
+
+        stream::iter(0..self.total_chunk_count)
+            .map(move |index| {
+                let source = source.clone();
+                async move { source.fetch(index).await }
+            })
+            .buffered(self.prefetch)
+            .boxed()
+    }
+}
+
+impl Stream for ResultStream {
+    type Item = Result<Vec<Option<String>>, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(row) = self.buffer.next() {
+                return Poll::Ready(Some(Ok(row)));
+            }
+            if self.chunks.is_none() {
+                self.chunks = Some(self.start());
+            }
+            let chunks = self.chunks.as_mut().expect("chunk stream started");
+            match chunks.try_poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(rows))) => self.buffer = rows.into_iter(),
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Everything needed to fetch a chunk, shared by in-flight requests.
+struct Source {
+    statements: Statements,
+    downloader: Downloader,
+    statement_id: String,
+    format: Option<Format>,
+    first: Option<ResultData>,
+    links: HashMap<i32, ExternalLink>,
+}
+
+impl Source {
+    /// Fetch a chunk, downloading it if the server returns an external link
+    /// for it rather than inline data.
+    async fn fetch(&self, index: i32) -> Result<Rows, Error> {
+        let link = match self.links.get(&index) {
+            Some(link) => Some(link.clone()),
+            None => {
+                let data = match &self.first {
+                    Some(first)
+                        if first.chunk_index.unwrap_or(0) == index
+                            && first.external_links.is_empty() =>
+                    {
+                        return Ok(first.data_array.clone())
+                    }
+                    _ => {
+                        self.statements
+                            .get_result_chunk(&self.statement_id, index)
+                            .await?
+                    }
+                };
+                match data
+                    .external_links
+                    .into_iter()
+                    .find(|l| l.chunk_index == index)
+                {
+                    Some(link) => Some(link),
+                    None => return Ok(data.data_array),
+                }
+            }
+        };
+        self.downloader
+            .download(&self.statement_id, index, link)
+            .await?
+            .json_rows()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use databricks_core::transport::mock::MockTransport;
+    use serde_json::json;
+
+    const CHUNKS: &str = "/api/2.0/sql/statements/st/result/chunks";
+
+    fn client(mock: &MockTransport) -> Client {
+        Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap()
+    }
+
+    async fn collect(mock: &MockTransport, response: serde_json::Value) -> Rows {
+        let response: Response = serde_json::from_value(response).unwrap();
+        ResultStream::new(client(mock), &response)
+            .collect_all()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_inline_chunks() {
+        let mock = MockTransport::new().on(
+            "GET",
+            &format!("{}/1", CHUNKS),
+            200,
+            json!({"chunk_index": 1, "data_array": [["2"]]}),
+        );
+        let rows = collect(
+            &mock,
+            json!({
+                "statement_id": "st",
+                "status": {"state": "SUCCEEDED"},
+                "manifest": {"format": "JSON_ARRAY", "total_chunk_count": 2},
+                "result": {"chunk_index": 0, "data_array": [["1"]]},
+            }),
+        )
+        .await;
+        assert_eq!(rows, [[Some("1".to_string())], [Some("2".to_string())]]);
+    }
+
+    #[tokio::test]
+    async fn test_external_chunks() {
+        let mock = MockTransport::new()
+            .on_raw("GET", "/chunk0", 200, br#"[["1"]]"#.to_vec())
+            .on_raw("GET", "/chunk1", 200, br#"[["2"]]"#.to_vec())
+            .on(
+                "GET",
+                &format!("{}/1", CHUNKS),
+                200,
+                json!({"external_links": [
+                    {"chunk_index": 1, "external_link": "https://storage.example.com/chunk1"},
+                ]}),
+            );
+        let rows = collect(
+            &mock,
+            json!({
+                "statement_id": "st",
+                "status": {"state": "SUCCEEDED"},
+                "manifest": {"format": "JSON_ARRAY", "total_chunk_count": 2},
+                "result": {"external_links": [
+                    {"chunk_index": 0, "external_link": "https://storage.example.com/chunk0"},
+                ]},
+            }),
+        )
+        .await;
+        assert_eq!(rows, [[Some("1".to_string())], [Some("2".to_string())]]);
+    }
+
+    #[tokio::test]
+    async fn test_empty_result() {
+        let mock = MockTransport::new();
+        let manifest_only = collect(
+            &mock,
+            json!({
+                "statement_id": "st",
+                "status": {"state": "SUCCEEDED"},
+                "manifest": {"format": "JSON_ARRAY", "total_chunk_count": 0, "total_row_count": 0},
+            }),
+        )
+        .await;
+        let empty_inline = collect(
+            &mock,
+            json!({
+                "statement_id": "st",
+                "status": {"state": "SUCCEEDED"},
+                "manifest": {"format": "JSON_ARRAY"},
+                "result": {"row_count": 0},
+            }),
+        )
+        .await;
+        assert!(manifest_only.is_empty());
+        assert!(empty_inline.is_empty());
+        assert!(mock.requests().is_empty());
+    }
+}
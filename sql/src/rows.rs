@@ -0,0 +1,476 @@
+use crate::types::{Column, Response};
+use crate::ResultStream;
+use databricks_core::{Client, Error};
+use futures::Stream;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use std::str::FromStr;
+
+/// A type that can be decoded from one result row.
+///
+/// Implemented for every `serde::Deserialize` type: structs are matched to
+/// columns by name, tuples and `Vec`s by position. Cells are converted using
+/// the column's `type_name`, so integer and decimal columns decode into
+/// numeric fields, and `ARRAY`, `MAP` and `STRUCT` columns (returned as JSON
+/// text) decode into `Vec`, maps or nested structs. Any column can also be
+/// read as its raw `String`.
+///
+/// ```
+/// #[derive(serde::Deserialize)]
+/// struct Trip {
+///     id: i64,
+///     fare: f64,
+///     tags: Vec<String>,
+///     note: Option<String>,
+/// }
+/// ```
+pub trait FromRow: Sized {
+    fn from_row(row: &Row<'_>) -> Result<Self, Error>;
+}
+
+impl<T: DeserializeOwned> FromRow for T {
+    fn from_row(row: &Row<'_>) -> Result<Self, Error> {
+        Ok(T::deserialize(RowDeserializer { row: *row })?)
+    }
+}
+
+/// One result row together with its column metadata.
+#[derive(Debug, Clone, Copy)]
+pub struct Row<'a> {
+    columns: &'a [Column],
+    values: &'a [Option<String>],
+}
+
+impl<'a> Row<'a> {
+    pub fn new(columns: &'a [Column], values: &'a [Option<String>]) -> Self {
+        Self { columns, values }
+    }
+
+    pub fn columns(&self) -> &'a [Column] {
+        self.columns
+    }
+
+    /// Raw text of a cell by column name.
+    pub fn raw(&self, name: &str) -> Option<&'a str> {
+        let index = self.index_of(name)?;
+        self.values.get(index)?.as_deref()
+    }
+
+    /// Decode a single cell by column name.
+    pub fn get<T: DeserializeOwned>(&self, name: &str) -> Result<T, Error> {
+        let index = self
+            .index_of(name)
+            .ok_or_else(|| Error::Other(format!("no column named {}", name)))?;
+        self.get_index(index)
+    }
+
+    /// Decode a single cell by position.
+    pub fn get_index<T: DeserializeOwned>(&self, index: usize) -> Result<T, Error> {
+        Ok(T::deserialize(self.cell(index))?)
+    }
+
+    /// Decode the whole row.
+    pub fn decode<T: FromRow>(&self) -> Result<T, Error> {
+        T::from_row(self)
+    }
+
+    fn index_of(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c.name == name)
+    }
+
+    fn cell(&self, index: usize) -> Cell<'a> {
+        Cell {
+            text: self.values.get(index).and_then(|v| v.as_deref()),
+            type_name: self
+                .columns
+                .get(index)
+                .and_then(|c| c.type_name.as_deref())
+                .unwrap_or("STRING"),
+        }
+    }
+}
+
+impl Response {
+    /// Column metadata from the manifest, if the statement returned rows.
+    pub fn columns(&self) -> &[Column] {
+        self.manifest
+            .as_ref()
+            .and_then(|m| m.schema.as_ref())
+            .map(|s| s.columns.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Decode every row of this succeeded statement, fetching the remaining
+    /// inline chunks or external links through `client` as the stream is
+    /// read.
+    ///
+    /// ```no_run
+    /// # async fn run(client: databricks_core::Client, response: databricks_sql::Response) -> Result<(), databricks_core::Error> {
+    /// use futures::TryStreamExt;
+    ///
+    /// let rows: Vec<(String, i64)> = response.rows::<(String, i64)>(&client).try_collect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rows<T: FromRow>(&self, client: &Client) -> impl Stream<Item = Result<T, Error>> {
+        ResultStream::new(client.clone(), self).rows()
+    }
+}
+
+struct RowDeserializer<'a> {
+    row: Row<'a>,
+}
+
+impl<'de, 'a> de::Deserializer<'de> for RowDeserializer<'a> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(Columns {
+            row: self.row,
+            index: 0,
+        })
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(Columns {
+            row: self.row,
+            index: 0,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+/// Walks the cells of a row as either a map (by name) or a sequence.
+struct Columns<'a> {
+    row: Row<'a>,
+    index: usize,
+}
+
+impl<'de, 'a> MapAccess<'de> for Columns<'a> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.row.columns.get(self.index) {
+            Some(column) => seed
+                .deserialize(column.name.as_str().into_deserializer())
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let cell = self.row.cell(self.index);
+        self.index += 1;
+        seed.deserialize(cell)
+    }
+}
+
+impl<'de, 'a> SeqAccess<'de> for Columns<'a> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.index >= self.row.values.len() {
+            return Ok(None);
+        }
+        let cell = self.row.cell(self.index);
+        self.index += 1;
+        seed.deserialize(cell).map(Some)
+    }
+}
+
+/// One cell, interpreted according to its column type.
+struct Cell<'a> {
+    text: Option<&'a str>,
+    type_name: &'a str,
+}
+
+impl<'a> Cell<'a> {
+    fn text(&self) -> Result<&'a str, serde_json::Error> {
+        self.text
+            .ok_or_else(|| de::Error::custom("unexpected NULL value"))
+    }
+
+    fn parse<T: FromStr>(&self) -> Result<T, serde_json::Error>
+    where
+        T::Err: std::fmt::Display,
+    {
+        let text = self.text()?;
+        text.trim().parse().map_err(|e| {
+            de::Error::custom(format!(
+                "invalid {} value {:?}: {}",
+                self.type_name, text, e
+            ))
+        })
+    }
+
+    /// Parse `ARRAY`, `MAP` and `STRUCT` values, which arrive as JSON text.
+    fn json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::from_str(self.text()?)
+    }
+
+    fn is_json(&self) -> bool {
+        matches!(self.type_name, "ARRAY" | "MAP" | "STRUCT")
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for Cell<'a> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let Some(text) = self.text else {
+            return visitor.visit_none();
+        };
+        match self.type_name {
+            "BOOLEAN" => visitor.visit_bool(self.parse()?),
+            "BYTE" | "SHORT" | "INT" | "LONG" => visitor.visit_i64(self.parse()?),
+            "FLOAT" | "DOUBLE" => visitor.visit_f64(self.parse()?),
+            _ if self.is_json() => de::Deserializer::deserialize_any(self.json()?, visitor),
+            _ => visitor.visit_str(text),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_str(self.text()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.text {
+            None => visitor.visit_none(),
+            Some(_) => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if self.is_json() {
+            return de::Deserializer::deserialize_enum(self.json()?, name, variants, visitor);
+        }
+        visitor.visit_enum(self.text()?.into_deserializer())
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_seq(self.json()?, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_map(self.json()?, visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_struct(self.json()?, name, fields, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bytes byte_buf unit_struct tuple tuple_struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+
+    fn column(name: &str, type_name: &str) -> Column {
+        Column {
+            name: name.into(),
+            type_name: Some(type_name.into()),
+            type_text: None,
+            type_precision: None,
+            type_scale: None,
+            position: 0,
+        }
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Address {
+        city: String,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Trip {
+        id: i32,
+        fare: f64,
+        tags: Vec<String>,
+        extra: HashMap<String, i64>,
+        address: Address,
+        picked_up: String,
+        note: Option<String>,
+    }
+
+    #[test]
+    fn test_decode_struct_by_column_name() {
+        let columns = vec![
+            column("fare", "DECIMAL"),
+            column("id", "INT"),
+            column("tags", "ARRAY"),
+            column("extra", "MAP"),
+            column("address", "STRUCT"),
+            column("picked_up", "TIMESTAMP"),
+            column("note", "STRING"),
+        ];
+        let values = vec![
+            Some("12.50".to_string()),
+            Some("7".to_string()),
+            Some(r#"["a","b"]"#.to_string()),
+            Some(r#"{"tolls":2}"#.to_string()),
+            Some(r#"{"city":"Oslo"}"#.to_string()),
+            Some("2024-01-01T10:00:00.000Z".to_string()),
+            None,
+        ];
+
+        let trip: Trip = Row::new(&columns, &values).decode().unwrap();
+        assert_eq!(
+            trip,
+            Trip {
+                id: 7,
+                fare: 12.5,
+                tags: vec!["a".into(), "b".into()],
+                extra: HashMap::from([("tolls".into(), 2)]),
+                address: Address {
+                    city: "Oslo".into()
+                },
+                picked_up: "2024-01-01T10:00:00.000Z".into(),
+                note: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_tuple_and_errors() {
+        let columns = vec![column("n", "LONG"), column("name", "STRING")];
+        let values = vec![Some("42".to_string()), None];
+        let row = Row::new(&columns, &values);
+
+        let (n, name): (i64, Option<String>) = row.decode().unwrap();
+        assert_eq!((n, name), (42, None));
+        assert_eq!(row.get::<String>("n").unwrap(), "42");
+        assert!(row.get::<String>("name").is_err());
+        assert!(row.get::<i64>("missing").is_err());
+
+        let bad = vec![Some("forty-two".to_string()), None];
+        assert!(Row::new(&columns, &bad).get::<i64>("n").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_response_rows_reads_every_chunk() {
+        use databricks_core::transport::mock::MockTransport;
+        use futures::TryStreamExt;
+
+        let mock = MockTransport::new().on(
+            "GET",
+            "/api/2.0/sql/statements/st1/result/chunks/1",
+            200,
+            serde_json::json!({"chunk_index": 1, "data_array": [["3"]]}),
+        );
+        let client = Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock)
+            .build()
+            .unwrap();
+        let response: Response = serde_json::from_value(serde_json::json!({
+            "statement_id": "st1",
+            "status": {"state": "SUCCEEDED"},
+            "manifest": {
+                "format": "JSON_ARRAY",
+                "schema": {"columns": [{"name": "n", "type_name": "INT", "position": 0}]},
+                "total_chunk_count": 2,
+            },
+            "result": {"chunk_index": 0, "data_array": [["1"], ["2"]]},
+        }))
+        .unwrap();
+
+        let rows: Vec<(i32,)> = response
+            .rows::<(i32,)>(&client)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(rows, [(1,), (2,), (3,)]);
+    }
+}
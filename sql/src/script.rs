@@ -0,0 +1,482 @@
+use crate::loader::read_file;
+use crate::types::{Request, Response};
+use crate::Statements;
+use databricks_core::runtime::Instant;
+use databricks_core::Error;
+use std::path::Path;
+use std::time::Duration;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// One statement of a script, as split by [`split_script`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptStatement {
+    /// The statement without its terminating `;` or leading comments.
+    pub text: String,
+    /// 1-based line of the script the statement starts on.
+    pub line: usize,
+}
+
+/// Split a SQL script into statements on `;`.
+///
+/// Semicolons inside string literals, quoted identifiers, `--` and `/* */`
+/// comments, and `$$`/`$tag$` bodies do not end a statement. Statements
+/// consisting only of comments are dropped.
+pub fn split_script(sql: &str) -> Vec<ScriptStatement> {
+    let bytes = sql.as_bytes();
+    let mut statements = Vec::new();
+    let mut code_start: Option<usize> = None;
+    let mut i = 0;
+
+    let mut push = |code_start: &mut Option<usize>, end: usize| {
+        if let Some(start) = code_start.take() {
+            statements.push(ScriptStatement {
+                text: sql[start..end].trim_end().to_string(),
+                line: sql[..start].matches('\n').count() + 1,
+            });
+        }
+    };
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = find(bytes, i + 2, b"\n").map_or(bytes.len(), |end| end + 1);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = skip_block_comment(bytes, i);
+            }
+            b';' => {
+                push(&mut code_start, i);
+                i += 1;
+            }
+            quote @ (b'\'' | b'"' | b'`') => {
+                code_start.get_or_insert(i);
+                i = skip_quoted(bytes, i, quote);
+            }
+            b'$' => {
+                code_start.get_or_insert(i);
+                i = match dollar_tag(bytes, i) {
+                    Some(tag) => {
+                        find(bytes, i + tag.len(), tag).map_or(bytes.len(), |end| end + tag.len())
+                    }
+                    None => i + 1,
+                };
+            }
+            b if b.is_ascii_whitespace() => i += 1,
+            _ => {
+                code_start.get_or_insert(i);
+                i += 1;
+            }
+        }
+    }
+    push(&mut code_start, bytes.len());
+    statements
+}
+
+/// Index of the first `needle` at or after `from`.
+fn find(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    bytes[from.min(bytes.len())..]
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|p| p + from)
+}
+
+/// Index just past a `/* */` comment starting at `start`. Comments nest.
+fn skip_block_comment(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    bytes.len()
+}
+
+/// Index just past a literal opened by `quote` at `start`. Backslash escapes
+/// the next character; a doubled quote closes and reopens the literal, which
+/// has the same effect.
+fn skip_quoted(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if quote != b'`' => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// The `$tag$` opening a dollar-quoted body at `start`, if there is one.
+fn dollar_tag(bytes: &[u8], start: usize) -> Option<&[u8]> {
+    let len = bytes[start + 1..]
+        .iter()
+        .position(|b| !(b.is_ascii_alphanumeric() || *b == b'_'))?;
+    let end = start + 1 + len;
+    let starts_with_digit = bytes.get(start + 1).is_some_and(u8::is_ascii_digit);
+    (bytes[end] == b'$' && !starts_with_digit).then(|| &bytes[start..=end])
+}
+
+/// What [`ScriptRunner`] does when a statement fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Skip the remaining statements.
+    #[default]
+    Stop,
+    /// Run the remaining statements anyway.
+    Continue,
+}
+
+/// Result of one statement of a script.
+#[derive(Debug)]
+pub struct StatementOutcome {
+    pub statement: ScriptStatement,
+    /// Catalog and schema the statement ran in, if set.
+    pub catalog: Option<String>,
+    pub schema: Option<String>,
+    pub elapsed: Duration,
+    pub result: Result<Response, Error>,
+}
+
+/// Outcome of a [`ScriptRunner::run`].
+#[derive(Debug, Default)]
+pub struct ScriptReport {
+    /// One entry per statement executed, in order.
+    pub outcomes: Vec<StatementOutcome>,
+    /// Statements not run because an earlier one failed.
+    pub skipped: Vec<ScriptStatement>,
+    pub elapsed: Duration,
+}
+
+impl ScriptReport {
+    /// Whether every statement ran and succeeded.
+    pub fn is_success(&self) -> bool {
+        self.skipped.is_empty() && self.failures().next().is_none()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = (&ScriptStatement, &Error)> {
+        self.outcomes
+            .iter()
+            .filter_map(|o| o.result.as_ref().err().map(|e| (&o.statement, e)))
+    }
+}
+
+/// Runs multi-statement SQL scripts on a warehouse.
+///
+/// The Statement Execution API has no sessions, so `USE CATALOG`, `USE
+/// SCHEMA` and `USE DATABASE` are tracked by the runner and applied to the
+/// following statements through [`Request::catalog`] and [`Request::schema`].
+///
+/// ```no_run
+/// # async fn run(statements: databricks_sql::Statements) -> Result<(), databricks_core::Error> {
+/// use databricks_sql::{ErrorPolicy, ScriptRunner};
+///
+/// let report = ScriptRunner::new(statements, "wh-id")
+///     .catalog("main")
+///     .on_error(ErrorPolicy::Continue)
+///     .run_file("migrations/001_init.sql")
+///     .await?;
+/// for (statement, error) in report.failures() {
+///     eprintln!("line {}: {}", statement.line, error);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ScriptRunner {
+    statements: Statements,
+    warehouse_id: String,
+    catalog: Option<String>,
+    schema: Option<String>,
+    on_error: ErrorPolicy,
+    poll_interval: Duration,
+    timeout: Duration,
+}
+
+impl ScriptRunner {
+    pub fn new(statements: Statements, warehouse_id: impl Into<String>) -> Self {
+        Self {
+            statements,
+            warehouse_id: warehouse_id.into(),
+            catalog: None,
+            schema: None,
+            on_error: ErrorPolicy::Stop,
+            poll_interval: POLL_INTERVAL,
+            timeout: TIMEOUT,
+        }
+    }
+
+    /// Initial catalog, until the script changes it.
+    pub fn catalog(mut self, catalog: impl Into<String>) -> Self {
+        self.catalog = Some(catalog.into());
+        self
+    }
+
+    /// Initial schema, until the script changes it.
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    pub fn on_error(mut self, policy: ErrorPolicy) -> Self {
+        self.on_error = policy;
+        self
+    }
+
+    /// Maximum time to wait for each statement (default 10 minutes).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Read and run a script file. The file is read through the client's
+    /// runtime, off the executor.
+    pub async fn run_file(&self, path: impl AsRef<Path>) -> Result<ScriptReport, Error> {
+        let path = path.as_ref();
+        let runtime = self.statements.client().runtime().as_ref();
+        let bytes = read_file(runtime, path.to_path_buf()).await?;
+        let script = String::from_utf8(bytes)
+            .map_err(|e| Error::Other(format!("failed to read {}: {}", path.display(), e)))?;
+        self.run(&script).await
+    }
+
+    /// Run each statement of `script` in order.
+    ///
+    /// Statement failures are recorded in the report rather than returned;
+    /// check [`ScriptReport::is_success`].
+    pub async fn run(&self, script: &str) -> Result<ScriptReport, Error> {
+        let start = Instant::now();
+        let mut report = ScriptReport::default();
+        let mut catalog = self.catalog.clone();
+        let mut schema = self.schema.clone();
+
+        let mut statements = split_script(script).into_iter();
+        for statement in statements.by_ref() {
+            let mut request = Request::new(statement.text.clone(), &self.warehouse_id);
+            request.catalog = catalog.clone();
+            request.schema = schema.clone();
+
+            let statement_start = Instant::now();
+            let result = self
+                .statements
+                .execute_wait(&request, self.poll_interval, self.timeout)
+                .await;
+
+            let failed = result.is_err();
+            if !failed {
+                match parse_use(&statement.text) {
+                    Some(Use::Catalog(name)) => {
+                        catalog = Some(name);
+                        schema = None;
+                    }
+                    Some(Use::Schema(Some(catalog_name), name)) => {
+                        catalog = Some(catalog_name);
+                        schema = Some(name);
+                    }
+                    Some(Use::Schema(None, name)) => schema = Some(name),
+                    None => {}
+                }
+            }
+
+            report.outcomes.push(StatementOutcome {
+                statement,
+                catalog: request.catalog,
+                schema: request.schema,
+                elapsed: statement_start.elapsed(),
+                result,
+            });
+            if failed && self.on_error == ErrorPolicy::Stop {
+                break;
+            }
+        }
+
+        report.skipped = statements.collect();
+        report.elapsed = start.elapsed();
+        Ok(report)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Use {
+    Catalog(String),
+    /// Optional catalog, and schema.
+    Schema(Option<String>, String),
+}
+
+/// Recognize `USE CATALOG c`, `USE SCHEMA|DATABASE [c.]s` and `USE [c.]s`.
+fn parse_use(statement: &str) -> Option<Use> {
+    let (keyword, rest) = next_word(statement)?;
+    if !keyword.eq_ignore_ascii_case("use") {
+        return None;
+    }
+    let (kind, name) = next_word(rest)?;
+    let is_catalog = kind.eq_ignore_ascii_case("catalog");
+    let name = if is_catalog
+        || kind.eq_ignore_ascii_case("schema")
+        || kind.eq_ignore_ascii_case("database")
+    {
+        name.trim()
+    } else {
+        rest.trim()
+    };
+
+    let mut parts = split_name(name)?;
+    match (is_catalog, parts.len()) {
+        (true, 1) => Some(Use::Catalog(parts.remove(0))),
+        (false, 1) => Some(Use::Schema(None, parts.remove(0))),
+        (false, 2) => {
+            let schema = parts.remove(1);
+            Some(Use::Schema(Some(parts.remove(0)), schema))
+        }
+        _ => None,
+    }
+}
+
+/// The first whitespace-separated word of `s` and the text after it.
+fn next_word(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start();
+    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+    (end > 0).then(|| s.split_at(end))
+}
+
+/// Split a possibly quoted, dotted name into its unquoted parts.
+fn split_name(name: &str) -> Option<Vec<String>> {
+    let mut parts = Vec::new();
+    let mut chars = name.chars().peekable();
+    loop {
+        let mut part = String::new();
+        match chars.peek() {
+            Some(&quote @ ('`' | '\'' | '"')) => {
+                chars.next();
+                loop {
+                    match chars.next()? {
+                        c if c == quote && chars.peek() == Some(&quote) => {
+                            chars.next();
+                            part.push(quote);
+                        }
+                        c if c == quote => break,
+                        c => part.push(c),
+                    }
+                }
+            }
+            _ => {
+                while let Some(&c) = chars.peek() {
+                    if c == '.' || c.is_whitespace() {
+                        break;
+                    }
+                    part.push(c);
+                    chars.next();
+                }
+            }
+        }
+        if part.is_empty() {
+            return None;
+        }
+        parts.push(part);
+        match chars.next() {
+            Some('.') => continue,
+            None => return Some(parts),
+            Some(_) => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_script() {
+        let script = "-- setup\nCREATE TABLE t (s STRING);\n\
+            INSERT INTO t VALUES ('a;b'), ('it''s'), ('back\\';slash');\n\
+            /* block; /* nested; */ still comment */\n\
+            CREATE FUNCTION f() RETURNS INT LANGUAGE PYTHON AS $$ return 1; $$;\n\
+            SELECT `weird;name`, \"q;\" FROM t -- trailing; comment\n\
+            ;\n-- only a comment;\n";
+
+        let statements = split_script(script);
+        let texts: Vec<&str> = statements.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "CREATE TABLE t (s STRING)",
+                "INSERT INTO t VALUES ('a;b'), ('it''s'), ('back\\';slash')",
+                "CREATE FUNCTION f() RETURNS INT LANGUAGE PYTHON AS $$ return 1; $$",
+                "SELECT `weird;name`, \"q;\" FROM t -- trailing; comment",
+            ]
+        );
+        let lines: Vec<usize> = statements.iter().map(|s| s.line).collect();
+        assert_eq!(lines, [2, 3, 5, 6]);
+    }
+
+    #[test]
+    fn test_parse_use() {
+        assert_eq!(
+            parse_use("USE CATALOG main"),
+            Some(Use::Catalog("main".into()))
+        );
+        assert_eq!(
+            parse_use("use schema `my cat`.sales"),
+            Some(Use::Schema(Some("my cat".into()), "sales".into()))
+        );
+        assert_eq!(
+            parse_use("USE DATABASE staging"),
+            Some(Use::Schema(None, "staging".into()))
+        );
+        assert_eq!(
+            parse_use("USE sales"),
+            Some(Use::Schema(None, "sales".into()))
+        );
+        assert_eq!(parse_use("SELECT 1"), None);
+        assert_eq!(parse_use("USE CATALOG a.b"), None);
+    }
+
+    #[tokio::test]
+    async fn test_run_file() {
+        use databricks_core::transport::mock::MockTransport;
+
+        let mock = MockTransport::new().on(
+            "POST",
+            "/api/2.0/sql/statements",
+            200,
+            serde_json::json!({"statement_id": "st", "status": {"state": "SUCCEEDED"}}),
+        );
+        let client = databricks_core::Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("script-{:016x}.sql", rand::random::<u64>()));
+        std::fs::write(&path, "SELECT 1;\nSELECT 2;\n").unwrap();
+
+        let report = ScriptRunner::new(Statements::new(client), "wh")
+            .run_file(&path)
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(report.is_success());
+        let sent: Vec<serde_json::Value> = mock
+            .requests()
+            .into_iter()
+            .filter_map(|r| r.body?.get("statement").cloned())
+            .collect();
+        assert_eq!(sent, ["SELECT 1", "SELECT 2"]);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::result_stream::ResultStream;
+use crate::statements::check_response;
+use crate::types::{Response, Status};
+use crate::Statements;
+use databricks_core::runtime::Instant;
+use databricks_core::{Client, Error};
+use std::time::Duration;
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A submitted statement.
+///
+/// Created by [`Statements::submit`], which lets the server hold the request
+/// for up to 50 seconds so short queries complete in a single round trip.
+/// Statements still running after that are polled from the client.
+///
+/// Dropping a handle whose statement has not finished cancels it in the
+/// background; call [`StatementHandle::detach`] to leave it running. A
+/// handle dropped outside of a runtime, e.g. after a blocking call has
+/// returned, cannot cancel its statement, which keeps running.
+///
+/// ```no_run
+/// # async fn run(statements: databricks_sql::Statements) -> Result<(), databricks_core::Error> {
+/// use futures::TryStreamExt;
+///
+/// let request = databricks_sql::Request::new("SELECT id, name FROM users", "wh-id");
+/// let mut handle = statements.submit(&request).await?;
+/// let rows: Vec<(i64, String)> = handle.result_stream().await?.rows().try_collect().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct StatementHandle {
+    client: Client,
+    statements: Statements,
+    response: Response,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+    detached: bool,
+}
+
+impl StatementHandle {
+    pub(crate) fn new(client: Client, response: Response) -> Self {
+        Self {
+            statements: Statements::new(client.clone()),
+            client,
+            response,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            timeout: None,
+            detached: false,
+        }
+    }
+
+    /// Interval between status polls once the server wait has elapsed
+    /// (default 1s).
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Give up waiting after `timeout`. The statement is cancelled when the
+    /// handle is then dropped.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn statement_id(&self) -> &str {
+        &self.response.statement_id
+    }
+
+    /// Last known status, as of submission or the latest poll.
+    pub fn status(&self) -> &Status {
+        &self.response.status
+    }
+
+    /// Last known response.
+    pub fn response(&self) -> &Response {
+        &self.response
+    }
+
+    /// Fetch the current status from the server.
+    pub async fn refresh(&mut self) -> Result<&Status, Error> {
+        self.response = self.statements.get(self.statement_id()).await?;
+        Ok(&self.response.status)
+    }
+
+    /// Poll until the statement finishes.
+    ///
+    /// Returns an error if it failed, was cancelled, or the handle's timeout
+    /// elapsed first.
+    pub async fn wait(&mut self) -> Result<&Response, Error> {
+        let start = Instant::now();
+
+        while !self.response.status.state.is_terminal() {
+            if self.timeout.is_some_and(|t| start.elapsed() > t) {
+                return Err(Error::Timeout("Statement execution timed out".into()));
+            }
+            self.client.runtime().sleep(self.poll_interval).await;
+            self.refresh().await?;
+        }
+
+        check_response(self.response.clone())?;
+        Ok(&self.response)
+    }
+
+    /// Wait for the statement and stream its rows across all chunks.
+    pub async fn result_stream(&mut self) -> Result<ResultStream, Error> {
+        self.wait().await?;
+        Ok(ResultStream::new(self.client.clone(), &self.response))
+    }
+
+    /// Cancel the statement.
+    pub async fn cancel(&mut self) -> Result<(), Error> {
+        self.statements.cancel(self.statement_id()).await?;
+        self.detached = true;
+        Ok(())
+    }
+
+    /// Stop tracking the statement without cancelling it, returning its id.
+    pub fn detach(mut self) -> String {
+        self.detached = true;
+        self.response.statement_id.clone()
+    }
+}
+
+impl Drop for StatementHandle {
+    fn drop(&mut self) {
+        if self.detached || self.response.status.state.is_terminal() {
+            return;
+        }
+        let statements = Statements::new(self.client.clone());
+        let statement_id = std::mem::take(&mut self.response.statement_id);
+        self.client.runtime().spawn(Box::pin(async move {
+            let _ = statements.cancel(&statement_id).await;
+        }));
+    }
+}
+
+/// Server-side `wait_timeout` for a client willing to wait `timeout`.
+///
+/// The API accepts `0s` (return immediately) or 5 to 50 seconds.
+pub(crate) fn server_wait_timeout(timeout: Option<Duration>) -> String {
+    match timeout.map(|t| t.as_secs()) {
+        Some(secs) if secs < 5 => "0s".into(),
+        Some(secs) => format!("{}s", secs.min(50)),
+        None => "50s".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Request;
+    use databricks_core::transport::mock::MockTransport;
+    use serde_json::json;
+
+    fn running() -> MockTransport {
+        MockTransport::new()
+            .on(
+                "POST",
+                "/api/2.0/sql/statements",
+                200,
+                json!({"statement_id": "st", "status": {"state": "RUNNING"}}),
+            )
+            .on("POST", "/api/2.0/sql/statements/st/cancel", 200, json!({}))
+    }
+
+    fn client(mock: &MockTransport) -> Client {
+        Client::builder()
+            .host("https://example.cloud.databricks.com")
+            .token("token")
+            .transport(mock.clone())
+            .build()
+            .unwrap()
+    }
+
+    fn cancelled(mock: &MockTransport) -> bool {
+        mock.requests()
+            .iter()
+            .any(|r| r.path == "/api/2.0/sql/statements/st/cancel")
+    }
+
+    #[tokio::test]
+    async fn test_drop_cancels_running_statement() {
+        let mock = running();
+        let statements = Statements::new(client(&mock));
+        let handle = statements
+            .submit(&Request::new("SELECT 1", "wh"))
+            .await
+            .unwrap();
+        drop(handle);
+
+        for _ in 0..100 {
+            if cancelled(&mock) {
+                return;
+            }
+            tokio::task::yield_now().await;
+        }
+        panic!("statement was not cancelled");
+    }
+
+    #[test]
+    fn test_drop_outside_runtime_does_not_cancel() {
+        let mock = running();
+        let statements = Statements::new(client(&mock));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let handle = runtime
+            .block_on(statements.submit(&Request::new("SELECT 1", "wh")))
+            .unwrap();
+        drop(handle);
+        assert!(!cancelled(&mock));
+    }
+}
@@ -1,23 +1,63 @@
-use crate::types::{Empty, EmptyResponse, Request, Response, ResultData, StatementState};
+use crate::export::{ExportFormat, Exporter};
+use crate::result_stream::ResultStream;
+use crate::rows::FromRow;
+use crate::statement_handle::{server_wait_timeout, StatementHandle};
+use crate::types::{
+    Empty, EmptyResponse, OnWaitTimeout, Request, Response, ResultData, StatementState,
+};
+use crate::warehouses::Warehouses;
+use databricks_core::runtime::Instant;
 use databricks_core::{Client, Error};
+use futures::io::AsyncWrite;
+use futures::Stream;
 use std::time::Duration;
-use tokio::time::sleep;
 
 const PATH: &str = "/api/2.0/sql/statements";
 
+/// Poll interval used while auto-starting a warehouse.
+const WAREHOUSE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
 pub struct Statements {
     client: Client,
+    auto_start: Option<Duration>,
 }
 
 impl Statements {
     pub fn new(client: Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            auto_start: None,
+        }
+    }
+
+    pub(crate) fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Start the target warehouse if it is stopped, waiting up to `timeout`
+    /// for it to run, before executing each statement.
+    ///
+    /// Adds a warehouse status check to every execution.
+    pub fn auto_start(mut self, timeout: Duration) -> Self {
+        self.auto_start = Some(timeout);
+        self
     }
 
     pub async fn execute(&self, request: &Request) -> Result<Response, Error> {
+        self.ensure_warehouse(request).await?;
         self.client.post(PATH, request).await
     }
 
+    async fn ensure_warehouse(&self, request: &Request) -> Result<(), Error> {
+        if let Some(timeout) = self.auto_start {
+            Warehouses::new(self.client.clone())
+                .ensure_running(&request.warehouse_id, WAREHOUSE_POLL_INTERVAL, timeout)
+                .await?;
+        }
+        Ok(())
+    }
+
     pub async fn get(&self, statement_id: &str) -> Result<Response, Error> {
         let path = format!("{}/{}", PATH, statement_id);
         self.client.get(&path).await
@@ -29,20 +69,38 @@ impl Statements {
         Ok(())
     }
 
+    /// Submit a statement and return a handle to track it.
+    ///
+    /// Unless the request sets them, the server waits up to 50s for the
+    /// result and keeps the statement running afterwards.
+    pub async fn submit(&self, request: &Request) -> Result<StatementHandle, Error> {
+        let response = self.execute(&with_server_wait(request, None)).await?;
+        Ok(StatementHandle::new(self.client.clone(), response))
+    }
+
     /// Execute and poll until completion
+    ///
+    /// The server holds the request for up to `timeout` (at most 50s)
+    /// before client polling starts.
     pub async fn execute_wait(
         &self,
         request: &Request,
         poll_interval: Duration,
         timeout: Duration,
     ) -> Result<Response, Error> {
-        let response = self.execute(request).await?;
+        self.ensure_warehouse(request).await?;
+        let start = Instant::now();
+        let response: Response = self
+            .client
+            .post(PATH, &with_server_wait(request, Some(timeout)))
+            .await?;
 
         if response.status.state.is_terminal() {
             return check_response(response);
         }
 
-        self.wait(&response.statement_id, poll_interval, timeout)
+        let remaining = timeout.saturating_sub(start.elapsed());
+        self.wait(&response.statement_id, poll_interval, remaining)
             .await
     }
 
@@ -56,6 +114,30 @@ impl Statements {
         self.client.get(&path).await
     }
 
+    /// Stream the rows of a succeeded statement across all result chunks.
+    pub fn result_stream(&self, response: &Response) -> ResultStream {
+        ResultStream::new(self.client.clone(), response)
+    }
+
+    /// Decode every row of a succeeded statement, across all result chunks.
+    pub fn rows<T: FromRow>(&self, response: &Response) -> impl Stream<Item = Result<T, Error>> {
+        self.result_stream(response).rows()
+    }
+
+    /// Write every row of a succeeded statement to `writer` in `format`.
+    ///
+    /// See [`Exporter`] for details and tuning.
+    pub async fn export<W: AsyncWrite + Unpin>(
+        &self,
+        response: &Response,
+        format: ExportFormat,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        Exporter::new(self.client.clone())
+            .export(response, format, writer)
+            .await
+    }
+
     /// Poll until the statement reaches a terminal state
     pub async fn wait(
         &self,
@@ -63,14 +145,14 @@ impl Statements {
         poll_interval: Duration,
         timeout: Duration,
     ) -> Result<Response, Error> {
-        let start = std::time::Instant::now();
+        let start = Instant::now();
 
         loop {
             if start.elapsed() > timeout {
                 return Err(Error::Timeout("Statement execution timed out".into()));
             }
 
-            sleep(poll_interval).await;
+            self.client.runtime().sleep(poll_interval).await;
 
             let response = self.get(statement_id).await?;
 
@@ -81,7 +163,19 @@ impl Statements {
     }
 }
 
-fn check_response(response: Response) -> Result<Response, Error> {
+/// `request` with a server wait, unless it already configures one.
+fn with_server_wait(request: &Request, timeout: Option<Duration>) -> Request {
+    let mut request = request.clone();
+    if request.wait_timeout.is_none() {
+        request.wait_timeout = Some(server_wait_timeout(timeout));
+    }
+    if request.on_wait_timeout.is_none() {
+        request.on_wait_timeout = Some(OnWaitTimeout::Continue);
+    }
+    request
+}
+
+pub(crate) fn check_response(response: Response) -> Result<Response, Error> {
     match response.status.state {
         StatementState::Succeeded => Ok(response),
         StatementState::Failed => {
@@ -1,3 +1,4 @@
+use crate::parameters::ParameterValue;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -70,6 +71,8 @@ pub struct Request {
     pub disposition: Option<Disposition>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<Format>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<Parameter>,
 }
 
 impl Request {
@@ -84,6 +87,7 @@ impl Request {
             row_limit: None,
             disposition: None,
             format: None,
+            parameters: Vec::new(),
         }
     }
 
@@ -102,10 +106,53 @@ impl Request {
         self
     }
 
+    /// What the server does when `wait_timeout` elapses before the
+    /// statement finishes.
+    pub fn on_wait_timeout(mut self, action: OnWaitTimeout) -> Self {
+        self.on_wait_timeout = Some(action);
+        self
+    }
+
     pub fn row_limit(mut self, limit: i64) -> Self {
         self.row_limit = Some(limit);
         self
     }
+
+    pub fn disposition(mut self, disposition: Disposition) -> Self {
+        self.disposition = Some(disposition);
+        self
+    }
+
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Bind a value to the named parameter `:name` in the statement.
+    ///
+    /// ```
+    /// # use databricks_sql::Request;
+    /// let req = Request::new("SELECT * FROM orders WHERE id = :id", "wh-id").bind("id", 42i64);
+    /// ```
+    pub fn bind<V: ParameterValue>(self, name: impl Into<String>, value: V) -> Self {
+        self.parameter(Parameter::new(name, value))
+    }
+
+    /// Add a prebuilt parameter, e.g. one created with [`Parameter::typed`].
+    pub fn parameter(mut self, parameter: Parameter) -> Self {
+        self.parameters.push(parameter);
+        self
+    }
+}
+
+/// A named statement parameter, referenced as `:name` in the statement text.
+#[derive(Debug, Clone, Serialize)]
+pub struct Parameter {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub sql_type: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -115,14 +162,14 @@ pub enum OnWaitTimeout {
     Cancel,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Disposition {
     Inline,
     ExternalLinks,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Format {
     JsonArray,
@@ -192,6 +239,22 @@ pub struct Manifest {
     pub total_chunk_count: Option<i32>,
     #[serde(default)]
     pub truncated: bool,
+    #[serde(default)]
+    pub format: Option<Format>,
+    /// Per-chunk metadata; present for all dispositions.
+    #[serde(default)]
+    pub chunks: Vec<ChunkInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChunkInfo {
+    pub chunk_index: i32,
+    #[serde(default)]
+    pub row_offset: Option<i64>,
+    #[serde(default)]
+    pub row_count: Option<i64>,
+    #[serde(default)]
+    pub byte_count: Option<i64>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -210,6 +273,10 @@ pub struct Column {
     #[serde(default)]
     pub type_text: Option<String>,
     #[serde(default)]
+    pub type_precision: Option<i32>,
+    #[serde(default)]
+    pub type_scale: Option<i32>,
+    #[serde(default)]
     pub position: i32,
 }
 
@@ -227,6 +294,37 @@ pub struct ResultData {
     pub next_chunk_index: Option<i32>,
     #[serde(default)]
     pub next_chunk_internal_link: Option<String>,
+    /// Presigned links, set when the statement used
+    /// [`Disposition::ExternalLinks`].
+    #[serde(default)]
+    pub external_links: Vec<ExternalLink>,
+    /// Base64-encoded Arrow IPC stream, set for inline
+    /// [`Format::ArrowStream`] results.
+    #[serde(default)]
+    pub attachment: Option<String>,
+}
+
+/// A presigned URL for one result chunk stored in cloud storage.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExternalLink {
+    pub external_link: String,
+    /// RFC 3339 timestamp after which the link no longer works.
+    #[serde(default)]
+    pub expiration: Option<String>,
+    pub chunk_index: i32,
+    #[serde(default)]
+    pub row_offset: Option<i64>,
+    #[serde(default)]
+    pub row_count: Option<i64>,
+    #[serde(default)]
+    pub byte_count: Option<i64>,
+    #[serde(default)]
+    pub next_chunk_index: Option<i32>,
+    #[serde(default)]
+    pub next_chunk_internal_link: Option<String>,
+    /// Headers the cloud storage request must carry (e.g. encryption keys).
+    #[serde(default)]
+    pub http_headers: std::collections::HashMap<String, String>,
 }
 
 // ============================================================================
@@ -424,6 +522,14 @@ pub struct UpdateVisualizationRequest {
     pub visualization_type: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListVisualizationsResponse {
+    #[serde(default)]
+    pub results: Vec<Visualization>,
+    #[serde(default)]
+    pub next_page_token: Option<String>,
+}
+
 // ============================================================================
 // Internal helpers
 // ============================================================================
@@ -1,10 +1,12 @@
 use crate::types::{
     CreateWarehouseRequest, CreateWarehouseResponse, EditWarehouseRequest, Empty, EmptyResponse,
     GetWarehousePermissionLevelsResponse, GetWorkspaceWarehouseConfigResponse,
-    ListWarehousesResponse, SetWorkspaceWarehouseConfigRequest, Warehouse, WarehousePermissions,
-    WarehousePermissionsRequest,
+    ListWarehousesResponse, SetWorkspaceWarehouseConfigRequest, State, Warehouse,
+    WarehousePermissions, WarehousePermissionsRequest,
 };
+use databricks_core::runtime::Instant;
 use databricks_core::{Client, Error};
+use std::time::Duration;
 
 const PATH: &str = "/api/2.0/sql/warehouses";
 const PERMISSIONS_PATH: &str = "/api/2.0/permissions/warehouses";
@@ -73,11 +75,13 @@ impl Warehouses {
         Ok(())
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(&self, warehouse_id: &str) -> Result<WarehousePermissions, Error> {
         let path = format!("{}/{}", PERMISSIONS_PATH, warehouse_id);
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         warehouse_id: &str,
@@ -86,6 +90,7 @@ impl Warehouses {
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         warehouse_id: &str,
@@ -95,6 +100,7 @@ impl Warehouses {
         self.client.put(&path, request).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         warehouse_id: &str,
@@ -103,4 +109,126 @@ impl Warehouses {
         let path = format!("{}/{}", PERMISSIONS_PATH, warehouse_id);
         self.client.patch(&path, request).await
     }
+
+    /// Create a warehouse and wait until it is running.
+    pub async fn create_and_wait(
+        &self,
+        request: &CreateWarehouseRequest,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<Warehouse, Error> {
+        let response = self.create(request).await?;
+        let id = response
+            .id
+            .ok_or_else(|| Error::Other("create warehouse returned no id".into()))?;
+        self.wait_for_state(&id, State::Running, poll_interval, timeout)
+            .await
+    }
+
+    /// Edit a warehouse and wait until it settles as running or stopped.
+    ///
+    /// Editing a running warehouse may restart it; a stopped warehouse stays
+    /// stopped.
+    pub async fn edit_and_wait(
+        &self,
+        id: &str,
+        request: &EditWarehouseRequest,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<Warehouse, Error> {
+        self.edit(id, request).await?;
+        let start = Instant::now();
+        loop {
+            let warehouse = self.get(id).await?;
+            if matches!(warehouse.state, State::Running | State::Stopped) {
+                return Ok(warehouse);
+            }
+            check_not_deleted(&warehouse)?;
+            if start.elapsed() > timeout {
+                return Err(Error::Timeout("Warehouse operation timed out".into()));
+            }
+            self.client.runtime().sleep(poll_interval).await;
+        }
+    }
+
+    /// Start a warehouse and wait until it is running.
+    pub async fn start_and_wait(
+        &self,
+        id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<Warehouse, Error> {
+        self.start(id).await?;
+        self.wait_for_state(id, State::Running, poll_interval, timeout)
+            .await
+    }
+
+    /// Stop a warehouse and wait until it is stopped.
+    pub async fn stop_and_wait(
+        &self,
+        id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<Warehouse, Error> {
+        self.stop(id).await?;
+        self.wait_for_state(id, State::Stopped, poll_interval, timeout)
+            .await
+    }
+
+    /// Make sure a warehouse is running, starting it if needed.
+    ///
+    /// A warehouse that is already starting is waited for, and one that is
+    /// stopping is started again once it has stopped.
+    pub async fn ensure_running(
+        &self,
+        id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<Warehouse, Error> {
+        let start = Instant::now();
+        loop {
+            let warehouse = self.get(id).await?;
+            match warehouse.state {
+                State::Running => return Ok(warehouse),
+                State::Stopped => self.start(id).await?,
+                State::Starting | State::Stopping => {}
+                State::Deleting | State::Deleted => check_not_deleted(&warehouse)?,
+            }
+            if start.elapsed() > timeout {
+                return Err(Error::Timeout("Warehouse operation timed out".into()));
+            }
+            self.client.runtime().sleep(poll_interval).await;
+        }
+    }
+
+    async fn wait_for_state(
+        &self,
+        id: &str,
+        target: State,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<Warehouse, Error> {
+        let start = Instant::now();
+        loop {
+            let warehouse = self.get(id).await?;
+            if warehouse.state == target {
+                return Ok(warehouse);
+            }
+            check_not_deleted(&warehouse)?;
+            if start.elapsed() > timeout {
+                return Err(Error::Timeout("Warehouse operation timed out".into()));
+            }
+            self.client.runtime().sleep(poll_interval).await;
+        }
+    }
+}
+
+fn check_not_deleted(warehouse: &Warehouse) -> Result<(), Error> {
+    match warehouse.state {
+        State::Deleting | State::Deleted => Err(Error::Other(format!(
+            "Warehouse {} is {:?}",
+            warehouse.id, warehouse.state
+        ))),
+        _ => Ok(()),
+    }
 }
@@ -81,6 +81,7 @@ impl Notebooks {
         Ok(())
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(
         &self,
         workspace_object_type: &str,
@@ -93,6 +94,7 @@ impl Notebooks {
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         workspace_object_type: &str,
@@ -105,6 +107,7 @@ impl Notebooks {
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         workspace_object_type: &str,
@@ -118,6 +121,7 @@ impl Notebooks {
         self.client.put(&path, request).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         workspace_object_type: &str,
@@ -30,6 +30,19 @@ impl Repos {
         Ok(response.repos)
     }
 
+    /// One page of repos; pass the previous page's `next_page_token` to
+    /// continue.
+    pub async fn list_page(&self, page_token: Option<&str>) -> Result<ListReposResponse, Error> {
+        match page_token {
+            Some(t) => {
+                self.client
+                    .get_with_query(PATH, &[("next_page_token", t)])
+                    .await
+            }
+            None => self.client.get(PATH).await,
+        }
+    }
+
     pub async fn update(&self, repo_id: i64, request: &UpdateRepo) -> Result<Repo, Error> {
         let path = format!("{}/{}", PATH, repo_id);
         self.client.patch(&path, request).await
@@ -40,11 +53,13 @@ impl Repos {
         self.client.delete_empty(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permissions(&self, repo_id: i64) -> Result<RepoPermissions, Error> {
         let path = format!("{}/{}", PERMISSIONS_PATH, repo_id);
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn get_permission_levels(
         &self,
         repo_id: i64,
@@ -53,6 +68,7 @@ impl Repos {
         self.client.get(&path).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn set_permissions(
         &self,
         repo_id: i64,
@@ -62,6 +78,7 @@ impl Repos {
         self.client.put(&path, request).await
     }
 
+    #[deprecated(note = "use `databricks_iam::ObjectPermissions`")]
     pub async fn update_permissions(
         &self,
         repo_id: i64,